@@ -0,0 +1,51 @@
+use crate::utils::msa::{Format, Msa};
+
+/// Rewrite an alignment in another format. Symbols are copied as they are,
+/// so this works for any alphabet.
+pub fn run(input: &str, from: Format, to: Format) -> Result<String, String> {
+    let msa: Msa<char> = Msa::parse(input, from)?;
+    Ok(msa.write(to)?.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output() {
+        assert_eq!(
+            run(
+                "CLUSTAL W multiple sequence alignment
+
+Rosalind_1    MEANL-Y
+Rosalind_2    PENAL-TY
+",
+                Format::Clustal,
+                Format::Stockholm
+            ),
+            Err("Row Rosalind_2 has a different length to the first row".to_string())
+        );
+        assert_eq!(
+            run(
+                "CLUSTAL W multiple sequence alignment
+
+Rosalind_1    MEANL-Y
+Rosalind_2    PENALTY
+                **  *
+",
+                Format::Clustal,
+                Format::Stockholm
+            ),
+            Ok("# STOCKHOLM 1.0
+Rosalind_1  MEANL-Y
+Rosalind_2  PENALTY
+//"
+            .to_string())
+        );
+        // Descriptions after the id would be read back as part of the sequence
+        assert_eq!(
+            run(">sp|P1 human\nMEAN-LY", Format::Fasta, Format::Phylip),
+            Ok("1 7\nsp|P1  MEAN-LY".to_string())
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use itertools::Itertools;
+
+use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::fasta::Fasta;
+use crate::utils::kmer::{max_k, KmerCounter};
+
+/// Count the `k`-mers across every record as tab separated `kmer count` lines
+pub fn run(input: &Fasta<Dna>, k: usize, canonical: bool) -> Result<String, String> {
+    let max_k = max_k::<DnaBase>();
+    if k == 0 || k > max_k {
+        return Err(format!("k must be between 1 and {}, got {}", max_k, k));
+    }
+    let counter = if canonical {
+        let mut counter = KmerCounter::canonical(k);
+        for (_, dna) in input.iter() {
+            counter.add(dna);
+        }
+        counter
+    } else {
+        KmerCounter::from_fasta(input, k)
+    };
+
+    let mut output = String::from("kmer\tcount\n");
+    for (kmer, count) in counter.iter() {
+        output.push_str(&format!("{}\t{}\n", kmer.iter().join(""), count))
+    }
+    Ok(output.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_output() {
+        assert_eq!(
+            run(
+                &Fasta::try_from(
+                    ">Rosalind_1
+AAAGTTT
+>Rosalind_2
+AAAC"
+                )
+                .unwrap(),
+                3,
+                true
+            ),
+            Ok("kmer\tcount
+AAA\t3
+AAC\t2
+AAG\t1
+ACT\t1"
+                .to_string())
+        );
+        let input = Fasta::try_from(">a\nACGT").unwrap();
+        assert_eq!(
+            run(&input, 0, false),
+            Err("k must be between 1 and 31, got 0".to_string())
+        );
+        assert!(run(&input, 32, true).is_err())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::utils::logo::{svg, ColourScheme};
+use crate::utils::msa::{Format, Msa};
+use crate::utils::profile::Profile;
+use crate::utils::sequence::Alphabet;
+use std::convert::TryFrom;
+
+/// Draw a sequence logo of an alignment as SVG, ignoring gaps
+pub fn run<T>(input: &str, format: Format, scheme: ColourScheme) -> Result<String, String>
+where
+    T: Alphabet + TryFrom<char>,
+    T::Error: ToString,
+{
+    let msa: Msa<T> = Msa::parse(input, format)?;
+    Ok(svg(&Profile::from_msa(&msa), scheme).trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::DnaBase;
+    use crate::utils::protein::AminoAcid;
+
+    #[test]
+    fn test_output() {
+        let logo = run::<DnaBase>(
+            ">Rosalind_1
+ACGT
+>Rosalind_2
+ACG-",
+            Format::Fasta,
+            ColourScheme::Nucleotide,
+        )
+        .unwrap();
+        assert!(logo.ends_with("</svg>"));
+        assert_eq!(logo.matches("font-weight=\"bold\"").count(), 4);
+        assert!(
+            run::<AminoAcid>(">a\nMEAN\n>b\nMEA", Format::Fasta, ColourScheme::Chemistry).is_err()
+        )
+    }
+}
@@ -0,0 +1,6 @@
+pub mod convert;
+pub mod kmers;
+pub mod logo;
+pub mod overlaps;
+pub mod scores;
+pub mod sketch;
@@ -0,0 +1,66 @@
+use crate::utils::digraph::Graph;
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+use crate::utils::graph_format::GraphFormat;
+
+/// Write the graph of records whose ends overlap by exactly `k` bases, or
+/// with `minimum` set by at least `k` bases, weighted by the longest overlap
+pub fn run(
+    input: &Fasta<Dna>,
+    k: usize,
+    minimum: bool,
+    format: GraphFormat,
+) -> Result<String, String> {
+    if k == 0 {
+        return Err("k must be at least 1".to_string());
+    }
+    let graph = if minimum {
+        Graph::minimum_overlap_graph(input, k)
+    } else {
+        Graph::overlap_graph(input, k)
+    };
+    Ok(graph.write(format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_output() {
+        let input = Fasta::try_from(">a\nAAATTT\n>b\nTTTCCC\n>c\nGGGAAA").unwrap();
+        assert_eq!(
+            run(&input, 3, false, GraphFormat::Dot),
+            Ok("digraph {
+    \"a\";
+    \"b\";
+    \"c\";
+    \"a\" -> \"b\" [label=\"3\"];
+    \"c\" -> \"a\" [label=\"3\"];
+}"
+            .to_string())
+        );
+        assert_eq!(
+            run(&input, 3, false, GraphFormat::Gfa),
+            Ok("H\tVN:Z:1.0
+S\ta\tAAATTT
+S\tb\tTTTCCC
+S\tc\tGGGAAA
+L\ta\t+\tb\t+\t3M
+L\tc\t+\ta\t+\t3M"
+                .to_string())
+        );
+        assert_eq!(
+            run(&input, 2, true, GraphFormat::Gfa)
+                .unwrap()
+                .lines()
+                .last(),
+            Some("L\tc\t+\ta\t+\t3M")
+        );
+        assert_eq!(
+            run(&input, 0, true, GraphFormat::Gfa),
+            Err("k must be at least 1".to_string())
+        )
+    }
+}
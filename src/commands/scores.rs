@@ -0,0 +1,95 @@
+use crate::utils::alignment::scoring::SubstitutionMatrix;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+use crate::utils::sequence::Sequence;
+use std::thread;
+
+/// Score the best local alignment of every pair of records, sharing the rows
+/// out between `threads` threads, and write the scores as a matrix
+pub fn run(
+    input: &Fasta<Protein>,
+    matrix: SubstitutionMatrix,
+    gap_open: i32,
+    gap_extend: i32,
+    threads: usize,
+) -> String {
+    let aligner = Aligner::affine(matrix, gap_open, gap_extend);
+    let records: Vec<&(String, Protein)> = input.iter().collect();
+    let threads = threads.max(1);
+
+    // Scores are symmetric, so each row only scores the records after it
+    let rows: Vec<(usize, Vec<i32>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let (aligner, records) = (&aligner, &records);
+                scope.spawn(move || {
+                    (thread..records.len())
+                        .step_by(threads)
+                        .map(|i| {
+                            let scorer = aligner.local_scorer(records[i].1.symbols());
+                            let scores = records[i..]
+                                .iter()
+                                .map(|(_, protein)| scorer.score(protein.symbols()))
+                                .collect();
+                            (i, scores)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    let mut matrix = vec![vec![0; records.len()]; records.len()];
+    for (i, scores) in rows {
+        for (offset, score) in scores.into_iter().enumerate() {
+            matrix[i][i + offset] = score;
+            matrix[i + offset][i] = score;
+        }
+    }
+
+    let mut output = format!("{}\n", records.len());
+    for ((name, _), scores) in records.iter().zip(matrix) {
+        output.push_str(name);
+        for score in scores {
+            output.push_str(&format!(" {}", score));
+        }
+        output.push('\n');
+    }
+    output.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_output() {
+        assert_eq!(
+            run(
+                &Fasta::try_from(
+                    ">Rosalind_1
+MEANLYPRTEINSTRING
+>Rosalind_2
+PLEASANTLYEINSTEIN
+>Rosalind_3
+PRTEINS"
+                )
+                .unwrap(),
+                SubstitutionMatrix::blosum62(),
+                11,
+                1,
+                2
+            ),
+            "3
+Rosalind_1 93 38 36
+Rosalind_2 38 89 20
+Rosalind_3 36 20 36"
+        )
+    }
+}
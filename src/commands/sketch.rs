@@ -0,0 +1,64 @@
+use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::fasta::Fasta;
+use crate::utils::kmer::max_k;
+use crate::utils::minhash::{distance_matrix, Sketch};
+
+/// Sketch every record and write their pairwise Mash distances as a PHYLIP distance matrix
+pub fn run(input: &Fasta<Dna>, k: usize, sketch_size: usize) -> Result<String, String> {
+    let max_k = max_k::<DnaBase>();
+    if k == 0 || k > max_k {
+        return Err(format!("k must be between 1 and {}, got {}", max_k, k));
+    }
+    if sketch_size == 0 {
+        return Err("The sketch size must be at least 1".to_string());
+    }
+    let (names, sketches): (Vec<String>, Vec<Sketch>) = Sketch::from_fasta(input, k, sketch_size)
+        .into_iter()
+        .unzip();
+
+    let mut output = format!("{}\n", names.len());
+    for (name, distances) in names.iter().zip(distance_matrix(&sketches)) {
+        output.push_str(name);
+        for distance in distances {
+            output.push_str(&format!(" {:.6}", distance));
+        }
+        output.push('\n');
+    }
+    Ok(output.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_output() {
+        assert_eq!(
+            run(
+                &Fasta::try_from(
+                    ">Rosalind_1
+AAAACCCC
+>Rosalind_2
+AAAACCCG"
+                )
+                .unwrap(),
+                4,
+                100
+            ),
+            Ok("2
+Rosalind_1 0.000000 0.055786
+Rosalind_2 0.055786 0.000000"
+                .to_string())
+        );
+        let input = Fasta::try_from(">a\nACGT").unwrap();
+        assert_eq!(
+            run(&input, 32, 100),
+            Err("k must be between 1 and 31, got 32".to_string())
+        );
+        assert_eq!(
+            run(&input, 4, 0),
+            Err("The sketch size must be at least 1".to_string())
+        )
+    }
+}
@@ -8,13 +8,17 @@ use clap::Parser;
 use wl_clipboard_rs::copy::{MimeType, Options, Source};
 
 mod options;
-mod problems;
-mod utils;
 
 use options::Args;
-use problems::*;
-use utils::fasta::Fasta;
-use utils::read_file;
+use rosalind_rust::commands::*;
+use rosalind_rust::problems::*;
+use rosalind_rust::utils::alignment::scoring::SubstitutionMatrix;
+use rosalind_rust::utils::dna::DnaBase;
+use rosalind_rust::utils::fasta::Fasta;
+use rosalind_rust::utils::graph_format::GraphFormat;
+use rosalind_rust::utils::msa::Format;
+use rosalind_rust::utils::protein::AminoAcid;
+use rosalind_rust::utils::read_file;
 
 fn make_solve<F>(f: F) -> Box<dyn Fn() -> String>
 where
@@ -23,9 +27,21 @@ where
     Box::new(f)
 }
 
+type Command = Box<dyn Fn(&Args) -> anyhow::Result<String>>;
+
+fn make_command<F>(f: F) -> Command
+where
+    F: Fn(&Args) -> anyhow::Result<String> + 'static,
+{
+    Box::new(f)
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
+    let overlap = args.k.unwrap_or(grph::DEFAULT_K);
+    let min_overlap = args.min_overlap;
+
     let problems = maplit::btreemap! {
         "dna" =>  make_solve(||dna::solve(&read_file("data/dna.txt"))),
         "rna" =>  make_solve(||rna::solve(&read_file("data/rna.txt"))),
@@ -38,7 +54,7 @@ fn main() -> anyhow::Result<()> {
         "iprb" => make_solve(||iprb::solve(&read_file("data/iprb.txt"))),
         "fib" => make_solve(||fib::solve(&read_file("data/fib.txt"))),
         "fibd" => make_solve(||fibd::solve(&read_file("data/fibd.txt"))),
-        "grph" => make_solve(||grph::solve(Fasta::load("data/grph.txt").unwrap())),
+        "grph" => make_solve(move ||grph::solve(Fasta::load("data/grph.txt").unwrap(), overlap, min_overlap)),
         "iev" => make_solve(||iev::solve(&read_file("data/iev.txt"))),
         "mrna" => make_solve(||mrna::solve(&read_file("data/mrna.txt"))),
         "lia" => make_solve(||lia::solve(&read_file("data/lia.txt"))),
@@ -47,18 +63,84 @@ fn main() -> anyhow::Result<()> {
         "mprt" => make_solve(||mprt::solve(&read_file("data/mprt.txt"))),
         "orf" => make_solve(||orf::solve(Fasta::load("data/orf.txt").unwrap())),
         "splc" => make_solve(||splc::solve(Fasta::load("data/splc.txt").unwrap())),
+        "suff" => make_solve(||suff::solve(&read_file("data/suff.txt"))),
+        "lrep" => make_solve(||lrep::solve(&read_file("data/lrep.txt"))),
+        "ling" => make_solve(||ling::solve(&read_file("data/ling.txt"))),
+        "mrep" => make_solve(||mrep::solve(&read_file("data/mrep.txt"))),
+        "trie" => make_solve(||trie::solve(&read_file("data/trie.txt"))),
+        "kmer" => make_solve(||kmer::solve(Fasta::load("data/kmer.txt").unwrap())),
+        "edit" => make_solve(||edit::solve(Fasta::load("data/edit.txt").unwrap())),
+        "edta" => make_solve(||edta::solve(Fasta::load("data/edta.txt").unwrap())),
+        "glob" => make_solve(||glob::solve(Fasta::load("data/glob.txt").unwrap())),
+        "gaff" => make_solve(||gaff::solve(Fasta::load("data/gaff.txt").unwrap())),
+        "laff" => make_solve(||laff::solve(Fasta::load("data/laff.txt").unwrap())),
+        "loca" => make_solve(||loca::solve(Fasta::load("data/loca.txt").unwrap())),
+        "sims" => make_solve(||sims::solve(Fasta::load("data/sims.txt").unwrap())),
+        "oap" => make_solve(||oap::solve(Fasta::load("data/oap.txt").unwrap())),
+        "smgb" => make_solve(||smgb::solve(Fasta::load("data/smgb.txt").unwrap())),
+        "ctea" => make_solve(||ctea::solve(Fasta::load("data/ctea.txt").unwrap())),
+        "mult" => make_solve(||mult::solve(Fasta::load("data/mult.txt").unwrap())),
+        "lcsq" => make_solve(||lcsq::solve(Fasta::load("data/lcsq.txt").unwrap())),
+        "scsp" => make_solve(||scsp::solve(&read_file("data/scsp.txt"))),
+        "sseq" => make_solve(||sseq::solve(Fasta::load("data/sseq.txt").unwrap())),
+        "lgis" => make_solve(||lgis::solve(&read_file("data/lgis.txt"))),
+        "bfs" => make_solve(||bfs::solve(&read_file("data/bfs.txt"))),
+        "cc" => make_solve(||cc::solve(&read_file("data/cc.txt"))),
+        "ts" => make_solve(||ts::solve(&read_file("data/ts.txt"))),
+        "dag" => make_solve(||dag::solve(&read_file("data/dag.txt"))),
+        "bip" => make_solve(||bip::solve(&read_file("data/bip.txt"))),
+        "dij" => make_solve(||dij::solve(&read_file("data/dij.txt"))),
+        "bf" => make_solve(||bf::solve(&read_file("data/bf.txt"))),
+        "nwc" => make_solve(||nwc::solve(&read_file("data/nwc.txt"))),
+        "sdag" => make_solve(||sdag::solve(&read_file("data/sdag.txt"))),
+        "scc" => make_solve(||scc::solve(&read_file("data/scc.txt"))),
+        "2sat" => make_solve(||two_sat::solve(&read_file("data/2sat.txt"))),
+        "sc" => make_solve(||sc::solve(&read_file("data/sc.txt"))),
+    };
+
+    let commands = maplit::btreemap! {
+        "convert" => make_command(|args| {
+            let from = Format::named(&args.from).map_err(anyhow::Error::msg)?;
+            let to = Format::named(&args.to).map_err(anyhow::Error::msg)?;
+            convert::run(&std::fs::read_to_string(args.input()?)?, from, to).map_err(anyhow::Error::msg)
+        }),
+        "kmers" => make_command(|args| kmers::run(&Fasta::load(args.input()?)?, args.k()?, args.canonical).map_err(anyhow::Error::msg)),
+        "logo" => make_command(|args| {
+            let input = std::fs::read_to_string(args.input()?)?;
+            let from = Format::named(&args.from).map_err(anyhow::Error::msg)?;
+            let scheme = args.colour_scheme()?;
+            if args.protein {
+                logo::run::<AminoAcid>(&input, from, scheme).map_err(anyhow::Error::msg)
+            } else {
+                logo::run::<DnaBase>(&input, from, scheme).map_err(anyhow::Error::msg)
+            }
+        }),
+        "overlaps" => make_command(|args| {
+            let format = GraphFormat::named(&args.graph_format).map_err(anyhow::Error::msg)?;
+            let k = args.k.unwrap_or(grph::DEFAULT_K);
+            overlaps::run(&Fasta::load(args.input()?)?, k, args.min_overlap, format).map_err(anyhow::Error::msg)
+        }),
+        "scores" => make_command(|args| {
+            let matrix = SubstitutionMatrix::named(&args.matrix).map_err(anyhow::Error::msg)?;
+            Ok(scores::run(&Fasta::load(args.input()?)?, matrix, args.gap_open, args.gap_extend, args.threads()))
+        }),
+        "sketch" => make_command(|args| sketch::run(&Fasta::load(args.input()?)?, args.k()?, args.sketch_size).map_err(anyhow::Error::msg)),
     };
 
     if let Some(problem) = problems.get(args.problem.as_str()) {
         let output = problem();
         println!("{}", output);
 
+        // Answers are copied for pasting into Rosalind, where a clipboard is available
         let opts = Options::new();
-        opts.copy(Source::Bytes(output.as_bytes()), MimeType::Autodetect)
-            .expect("Failed to copy to clipboard");
+        if let Err(e) = opts.copy(Source::Bytes(output.as_bytes()), MimeType::Autodetect) {
+            eprintln!("Warning: failed to copy to clipboard: {}", e);
+        }
+    } else if let Some(command) = commands.get(args.problem.as_str()) {
+        println!("{}", command(&args)?);
     } else {
         eprintln!("Problem {} not matched, options are:", args.problem);
-        for problem in problems.keys() {
+        for problem in problems.keys().chain(commands.keys()) {
             eprintln!("{}", problem);
         }
     }
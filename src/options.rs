@@ -1,7 +1,95 @@
 use clap::Parser;
+use rosalind_rust::utils::logo::ColourScheme;
 
 #[derive(Debug, Clone, Parser)]
 pub struct Args {
-    /// The problem to solve.
+    /// The problem or command to run.
     pub problem: String,
+
+    /// Input file for commands.
+    #[clap(short, long)]
+    pub input: Option<String>,
+
+    /// Length of the k-mers to use, or of the overlaps for grph.
+    #[clap(short)]
+    pub k: Option<usize>,
+
+    /// Join records overlapping by at least k bases rather than exactly k, for
+    /// grph and overlaps.
+    #[clap(long)]
+    pub min_overlap: bool,
+
+    /// Treat each k-mer and its reverse complement as the same.
+    #[clap(long)]
+    pub canonical: bool,
+
+    /// Number of hashes to keep in each MinHash sketch.
+    #[clap(long, default_value = "1000")]
+    pub sketch_size: usize,
+
+    /// Substitution matrix for protein alignments.
+    #[clap(long, default_value = "blosum62")]
+    pub matrix: String,
+
+    /// Penalty for the first position of a gap.
+    #[clap(long, default_value = "11")]
+    pub gap_open: i32,
+
+    /// Penalty for each further position of a gap.
+    #[clap(long, default_value = "1")]
+    pub gap_extend: i32,
+
+    /// Format of the input alignment: fasta, clustal, stockholm or phylip.
+    #[clap(long, default_value = "fasta")]
+    pub from: String,
+
+    /// Format to write alignments in.
+    #[clap(long, default_value = "clustal")]
+    pub to: String,
+
+    /// Format to write graphs in: dot, graphml or gfa.
+    #[clap(long, default_value = "gfa")]
+    pub graph_format: String,
+
+    /// Treat the input as protein rather than DNA.
+    #[clap(long)]
+    pub protein: bool,
+
+    /// Colour scheme for sequence logos: nucleotide, chemistry, hydrophobicity
+    /// or monochrome. Defaults to nucleotide for DNA and chemistry for protein.
+    #[clap(long)]
+    pub colours: Option<String>,
+
+    /// Number of threads to use, defaulting to the available parallelism.
+    #[clap(long)]
+    pub threads: Option<usize>,
+}
+
+impl Args {
+    pub fn input(&self) -> anyhow::Result<&str> {
+        self.input
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("{} requires an --input file", self.problem))
+    }
+
+    pub fn k(&self) -> anyhow::Result<usize> {
+        self.k
+            .ok_or_else(|| anyhow::anyhow!("{} requires a value for -k", self.problem))
+    }
+
+    pub fn colour_scheme(&self) -> anyhow::Result<ColourScheme> {
+        match &self.colours {
+            Some(name) => ColourScheme::named(name).map_err(anyhow::Error::msg),
+            None if self.protein => Ok(ColourScheme::Chemistry),
+            None => Ok(ColourScheme::Nucleotide),
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+    }
 }
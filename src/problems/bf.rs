@@ -0,0 +1,41 @@
+use crate::utils::digraph::Graph;
+use crate::utils::shortest_path::bellman_ford;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    bellman_ford(&graph, 0)
+        .expect("Graph has a negative cycle")
+        .distances()
+        .iter()
+        .map(|distance| distance.map_or("x".to_string(), |d| d.to_string()))
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "9 13
+1 2 10
+3 2 1
+3 4 1
+4 5 3
+5 6 -1
+7 6 -1
+8 7 1
+1 8 8
+7 2 -4
+2 6 2
+6 3 -2
+9 5 -10
+9 4 7"
+            ),
+            "0 5 5 6 9 7 9 8 x"
+        )
+    }
+}
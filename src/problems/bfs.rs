@@ -0,0 +1,32 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::bfs_distances;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    bfs_distances(&graph, 0)
+        .iter()
+        .map(|distance| distance.map_or(-1, |d| d as i64))
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "6 6
+4 6
+6 5
+4 3
+3 5
+2 1
+1 4"
+            ),
+            "0 -1 2 1 3 2"
+        )
+    }
+}
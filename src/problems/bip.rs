@@ -0,0 +1,36 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::is_bipartite;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    Graph::from_edge_lists(input, false)
+        .unwrap()
+        .iter()
+        .map(|graph| if is_bipartite(graph) { "1" } else { "-1" })
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "2
+
+3 3
+1 2
+3 2
+3 1
+
+4 3
+1 4
+3 1
+1 2"
+            ),
+            "-1 1"
+        )
+    }
+}
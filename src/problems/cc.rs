@@ -0,0 +1,35 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::connected_components;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, false).unwrap();
+    connected_components(&graph).len().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "12 13
+1 2
+1 5
+5 9
+5 10
+9 10
+3 4
+3 7
+3 8
+4 8
+7 11
+8 11
+11 12
+8 12"
+            ),
+            "3"
+        )
+    }
+}
@@ -1,56 +1,40 @@
-use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::dna::DnaBase;
 use crate::utils::fasta::Fasta;
+use crate::utils::gapped::GappedSequence;
+use crate::utils::msa::Msa;
+use crate::utils::profile::Profile;
+use crate::utils::sequence::Alphabet;
 use itertools::Itertools;
+use std::convert::TryFrom;
 
-pub fn solve(input: Fasta<Dna>) -> String {
-    let len = input.iter().next().unwrap().1.len();
-    let mut a = vec![0; len];
-    let mut c = vec![0; len];
-    let mut g = vec![0; len];
-    let mut t = vec![0; len];
+/// Summarise aligned sequences, ignoring gaps. Columns of only gaps have a
+/// gap as their consensus, and sequences of different lengths are reported
+/// as an error.
+pub fn solve(input: Fasta<GappedSequence<DnaBase>>) -> String {
+    let msa = match Msa::try_from(input) {
+        Ok(msa) => msa,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let profile = Profile::from_msa(&msa);
 
-    for (_, sequence) in input.iter() {
-        for (i, base) in sequence.iter().enumerate() {
-            match base {
-                DnaBase::A => a[i] += 1,
-                DnaBase::C => c[i] += 1,
-                DnaBase::G => g[i] += 1,
-                DnaBase::T => t[i] += 1,
-            }
-        }
+    let mut output = profile
+        .consensus()
+        .iter()
+        .map(|base| base.map_or("-".to_string(), |base| base.to_string()))
+        .join("");
+    for &base in DnaBase::SYMBOLS {
+        output.push_str(&format!(
+            "\n{}: {}",
+            base,
+            profile.symbol_counts(base).iter().join(" ")
+        ));
     }
-
-    let mut consensus = Vec::new();
-
-    for i in 0..len {
-        consensus.push(
-            [
-                (a[i], DnaBase::A),
-                (c[i], DnaBase::C),
-                (g[i], DnaBase::G),
-                (t[i], DnaBase::T),
-            ]
-            .iter()
-            .max_by_key(|x| x.0)
-            .unwrap()
-            .1,
-        )
-    }
-
-    format!(
-        "{}\nA: {}\nC: {}\nG: {}\nT: {}",
-        consensus.iter().join(""),
-        a.iter().join(" "),
-        c.iter().join(" "),
-        g.iter().join(" "),
-        t.iter().join(" "),
-    )
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::convert::TryFrom;
 
     #[test]
     fn test_answer() {
@@ -81,4 +65,44 @@ G: 1 1 6 3 0 1 0 0
 T: 1 5 0 0 0 1 1 6"
         )
     }
+
+    #[test]
+    fn gaps_are_not_counted() {
+        assert_eq!(
+            solve(Fasta::try_from(">a\nAC-T\n>b\nA-GT\n>c\nACG-").unwrap()),
+            "ACGT
+A: 3 0 0 0
+C: 0 2 0 0
+G: 0 0 2 0
+T: 0 0 0 2"
+        );
+        assert_eq!(
+            solve(Fasta::try_from(">a\nA-\n>b\nC-").unwrap()),
+            "A-
+A: 1 0
+C: 1 0
+G: 0 0
+T: 0 0"
+        )
+    }
+
+    #[test]
+    fn ties_take_the_earliest_base() {
+        assert_eq!(
+            solve(Fasta::try_from(">a\nAT\n>b\nTA").unwrap()),
+            "AA
+A: 1 1
+C: 0 0
+G: 0 0
+T: 1 1"
+        )
+    }
+
+    #[test]
+    fn unequal_lengths_are_reported() {
+        assert_eq!(
+            solve(Fasta::try_from(">a\nACGT\n>b\nACG").unwrap()),
+            "Error: Row b has a different length to the first row"
+        )
+    }
 }
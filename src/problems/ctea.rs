@@ -0,0 +1,36 @@
+use crate::utils::alignment::scoring::MatchMismatch;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein);
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    let aligner = Aligner::new(MatchMismatch::new(0, -1), 1);
+
+    format!("{}", aligner.count_optimal(protein1, protein2, 134_217_727))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_78
+PLEASANTLY
+>Rosalind_33
+MEANLY"
+                )
+                .unwrap()
+            ),
+            "4"
+        )
+    }
+}
@@ -0,0 +1,40 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::is_acyclic;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    Graph::from_edge_lists(input, true)
+        .unwrap()
+        .iter()
+        .map(|graph| if is_acyclic(graph) { "1" } else { "-1" })
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "3
+
+2 1
+1 2
+
+4 4
+4 1
+1 2
+2 3
+3 1
+
+4 3
+4 3
+3 2
+2 1"
+            ),
+            "1 -1 1"
+        )
+    }
+}
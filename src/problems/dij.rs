@@ -0,0 +1,38 @@
+use crate::utils::digraph::Graph;
+use crate::utils::shortest_path::dijkstra;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    dijkstra(&graph, 0)
+        .unwrap()
+        .distances()
+        .iter()
+        .map(|distance| distance.unwrap_or(-1))
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "6 10
+3 4 4
+1 2 4
+1 3 2
+2 3 3
+6 3 2
+3 5 5
+5 4 1
+3 2 1
+2 4 2
+2 5 3"
+            ),
+            "0 3 2 5 6 -1"
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::alignment::edit_distance;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+use crate::utils::sequence::Sequence;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein.symbols());
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    format!("{}", edit_distance(protein1, protein2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_39
+PLEASANTLY
+>Rosalind_11
+MEANLY"
+                )
+                .unwrap()
+            ),
+            "5"
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use crate::utils::alignment::scoring::MatchMismatch;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein);
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    // Unit cost edits, so the best alignment score is the negated edit distance
+    let aligner = Aligner::new(MatchMismatch::new(0, -1), 1);
+    let alignment = aligner.global(protein1, protein2);
+
+    format!(
+        "{}\n{}\n{}",
+        -alignment.score(),
+        alignment.first(),
+        alignment.second()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_43
+PRETTY
+>Rosalind_97
+PRTTEIN"
+                )
+                .unwrap()
+            ),
+            "4
+PRET-TY
+PRTTEIN"
+        )
+    }
+}
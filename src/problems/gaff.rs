@@ -0,0 +1,44 @@
+use crate::utils::alignment::scoring::SubstitutionMatrix;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein);
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    let aligner = Aligner::affine(SubstitutionMatrix::blosum62(), 11, 1);
+    let alignment = aligner.global(protein1, protein2);
+
+    format!(
+        "{}\n{}\n{}",
+        alignment.score(),
+        alignment.first(),
+        alignment.second()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_49
+PRTEINS
+>Rosalind_47
+PRTWPSEIN"
+                )
+                .unwrap()
+            ),
+            "8
+PRT---EINS
+PRTWPSEIN-"
+        )
+    }
+}
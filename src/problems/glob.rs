@@ -0,0 +1,36 @@
+use crate::utils::alignment::scoring::SubstitutionMatrix;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein);
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    let aligner = Aligner::new(SubstitutionMatrix::blosum62(), 5);
+
+    format!("{}", aligner.global(protein1, protein2).score())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_67
+PLEASANTLY
+>Rosalind_17
+MEANLY"
+                )
+                .unwrap()
+            ),
+            "8"
+        )
+    }
+}
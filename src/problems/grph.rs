@@ -1,16 +1,32 @@
-use crate::utils::digraph::Digraph;
+use crate::utils::digraph::Graph;
 use crate::utils::dna::Dna;
 use crate::utils::fasta::Fasta;
 
-pub fn solve(input: Fasta<Dna>) -> String {
-    let k = 3;
-    let graph: Digraph<&Dna> = Digraph::overlap_graph_from_fasta_dna(&input, k);
-    let list = graph.adjacency_list();
+/// Rosalind asks for overlaps of length 3
+pub const DEFAULT_K: usize = 3;
+
+/// List the pairs of records overlapping by exactly `k` bases, or with
+/// `minimum` set by at least `k` bases
+pub fn solve(input: Fasta<Dna>, k: usize, minimum: bool) -> String {
+    if k == 0 {
+        return "Error: k must be at least 1".to_string();
+    }
+    let graph = if minimum {
+        Graph::minimum_overlap_graph(&input, k)
+    } else {
+        Graph::overlap_graph(&input, k)
+    };
+    let mut list: Vec<(&str, &str)> = graph
+        .edges()
+        .iter()
+        .map(|edge| (graph.node(edge.from).name(), graph.node(edge.to).name()))
+        .collect();
+    list.sort();
 
     let mut s = String::new();
 
     for (node1, node2) in list {
-        s.push_str(&format!("{} {}\n", node1.name(), node2.name()))
+        s.push_str(&format!("{} {}\n", node1, node2))
     }
     s.trim().to_string()
 }
@@ -36,11 +52,23 @@ AAATCCC
 >Rosalind_5013
 GGGTGGG"
                 )
-                .unwrap()
+                .unwrap(),
+                DEFAULT_K,
+                false
             ),
             "Rosalind_0498 Rosalind_0442
 Rosalind_0498 Rosalind_2391
 Rosalind_2391 Rosalind_2323"
         )
     }
+
+    #[test]
+    fn empty_overlaps_are_reported() {
+        for &minimum in &[false, true] {
+            assert_eq!(
+                solve(Fasta::try_from(">a\nACGT\n>b\nTTTT").unwrap(), 0, minimum),
+                "Error: k must be at least 1"
+            )
+        }
+    }
 }
@@ -0,0 +1,44 @@
+use crate::utils::alignment::scoring::SubstitutionMatrix;
+use crate::utils::alignment::Aligner;
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut proteins = input.iter().map(|(_, protein)| protein);
+    let protein1 = proteins.next().unwrap();
+    let protein2 = proteins.next().unwrap();
+
+    let aligner = Aligner::affine(SubstitutionMatrix::blosum62(), 11, 1);
+    let alignment = aligner.local(protein1, protein2);
+
+    format!(
+        "{}\n{}\n{}",
+        alignment.score(),
+        alignment.first().replace('-', ""),
+        alignment.second().replace('-', "")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_8
+PLEASANTLY
+>Rosalind_18
+MEANLY"
+                )
+                .unwrap()
+            ),
+            "12
+LEAS
+MEAN"
+        )
+    }
+}
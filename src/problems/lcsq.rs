@@ -0,0 +1,36 @@
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+use crate::utils::sequence::Sequence;
+use crate::utils::subsequence::longest_common_subsequence;
+
+pub fn solve(input: Fasta<Dna>) -> String {
+    let mut dnas = input.iter().map(|(_, dna)| dna);
+    let dna1 = dnas.next().unwrap();
+    let dna2 = dnas.next().unwrap();
+
+    let common = longest_common_subsequence(dna1.symbols(), dna2.symbols());
+
+    format!("{}", Dna::from(common))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_23
+AACCTTGG
+>Rosalind_64
+ACACTGTGA"
+                )
+                .unwrap()
+            ),
+            "AACTTG"
+        )
+    }
+}
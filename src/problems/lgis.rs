@@ -0,0 +1,39 @@
+use crate::utils::subsequence::{longest_decreasing_subsequence, longest_increasing_subsequence};
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let permutation: Vec<u32> = input
+        .lines()
+        .nth(1)
+        .unwrap()
+        .split_whitespace()
+        .map(|x| x.parse().unwrap())
+        .collect();
+
+    format!(
+        "{}\n{}",
+        longest_increasing_subsequence(&permutation)
+            .iter()
+            .join(" "),
+        longest_decreasing_subsequence(&permutation)
+            .iter()
+            .join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "5
+5 1 4 2 3"
+            ),
+            "1 2 3
+5 4 3"
+        )
+    }
+}
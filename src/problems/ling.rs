@@ -0,0 +1,22 @@
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::suffix_tree::SuffixTree;
+
+pub fn solve(input: &str) -> String {
+    let dna = Dna::try_from(input).unwrap();
+
+    let tree = SuffixTree::new(&dna);
+
+    format!("{}", tree.linguistic_complexity(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(solve("ATTTGGATT"), "0.875")
+    }
+}
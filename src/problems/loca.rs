@@ -0,0 +1,44 @@
+use crate::utils::alignment::scoring::SubstitutionMatrix;
+use crate::utils::alignment::{Aligner, Mode};
+use crate::utils::fasta::Fasta;
+use crate::utils::protein::Protein;
+
+pub fn solve(input: Fasta<Protein>) -> String {
+    let mut sequences = input.iter().map(|(_, sequence)| sequence);
+    let s = sequences.next().unwrap();
+    let t = sequences.next().unwrap();
+
+    let aligner = Aligner::new(SubstitutionMatrix::pam250(), 5);
+    let alignment = aligner.align(s, t, Mode::Local);
+
+    format!(
+        "{}\n{}\n{}",
+        alignment.score(),
+        alignment.first().replace('-', ""),
+        alignment.second().replace('-', "")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_80
+MEANLYPRTEINSTRING
+>Rosalind_21
+PLEASANTLYEINSTEIN"
+                )
+                .unwrap()
+            ),
+            "23
+MEANLYPRTEINSTRIN
+LEASANTLYEINSTEIN"
+        )
+    }
+}
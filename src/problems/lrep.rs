@@ -0,0 +1,48 @@
+use itertools::Itertools;
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::suffix_tree::SuffixTree;
+
+pub fn solve(input: &str) -> String {
+    let mut lines = input.lines();
+    let dna = Dna::try_from(lines.next().unwrap().trim_end_matches('$')).unwrap();
+    let k = lines.next().unwrap().trim().parse::<usize>().unwrap();
+
+    // The given tree edges are not needed as we build the suffix tree ourselves
+    let tree = SuffixTree::new(&dna);
+
+    tree.longest_repeat(k).unwrap_or_default().iter().join("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "CATACATAC$
+2
+node1 node2 1 1
+node1 node7 2 1
+node1 node14 3 3
+node1 node17 10 1
+node2 node3 2 4
+node2 node6 10 1
+node3 node4 6 5
+node3 node5 10 1
+node7 node8 3 3
+node7 node11 5 1
+node8 node9 6 5
+node8 node10 10 1
+node11 node12 6 5
+node11 node13 10 1
+node14 node15 6 5
+node14 node16 10 1"
+            ),
+            "CATAC"
+        )
+    }
+}
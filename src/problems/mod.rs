@@ -1,20 +1,53 @@
+pub mod bf;
+pub mod bfs;
+pub mod bip;
+pub mod cc;
 pub mod cons;
+pub mod ctea;
+pub mod dag;
+pub mod dij;
 pub mod dna;
+pub mod edit;
+pub mod edta;
 pub mod fib;
 pub mod fibd;
+pub mod gaff;
 pub mod gc;
+pub mod glob;
 pub mod grph;
 pub mod hamm;
 pub mod iev;
 pub mod iprb;
+pub mod kmer;
+pub mod laff;
+pub mod lcsq;
+pub mod lgis;
 pub mod lia;
+pub mod ling;
+pub mod loca;
+pub mod lrep;
 pub mod mprt;
+pub mod mrep;
 pub mod mrna;
+pub mod mult;
+pub mod nwc;
+pub mod oap;
 pub mod orf;
 pub mod prob;
 pub mod prot;
 pub mod prtm;
 pub mod revc;
 pub mod rna;
+pub mod sc;
+pub mod scc;
+pub mod scsp;
+pub mod sdag;
+pub mod sims;
+pub mod smgb;
 pub mod splc;
+pub mod sseq;
 pub mod subs;
+pub mod suff;
+pub mod trie;
+pub mod ts;
+pub mod two_sat;
@@ -0,0 +1,30 @@
+use itertools::Itertools;
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::suffix_tree::SuffixTree;
+
+pub fn solve(input: &str) -> String {
+    let dna = Dna::try_from(input).unwrap();
+
+    let tree = SuffixTree::new(&dna);
+
+    tree.maximal_repeats(20)
+        .iter()
+        .map(|repeat| repeat.iter().join(""))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve("TAGAGATAGAATGGGTCCAGAGTTTTGTAATTTCCATGGGTCCAGAGTTTTGTAATTTATTATATAGAGATAGAATGGGTCCAGAGTTTTGTAATTTCCATGGGTCCAGAGTTTTGTAATTTAT"),
+            "TAGAGATAGAATGGGTCCAGAGTTTTGTAATTTCCATGGGTCCAGAGTTTTGTAATTTAT
+ATGGGTCCAGAGTTTTGTAATTT"
+        )
+    }
+}
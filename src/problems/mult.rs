@@ -0,0 +1,46 @@
+use crate::utils::alignment::multiple::{MultipleAligner, TreeMethod};
+use crate::utils::alignment::scoring::MatchMismatch;
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+use itertools::Itertools;
+
+pub fn solve(input: Fasta<Dna>) -> String {
+    let aligner = MultipleAligner::new(MatchMismatch::new(0, -1), 1, TreeMethod::Upgma);
+    let alignment = aligner.align_exact(&input).unwrap();
+
+    format!(
+        "{}\n{}",
+        aligner.sum_of_pairs(&alignment),
+        alignment.iter().map(|(_, row)| row).join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_7
+ATATCCG
+>Rosalind_35
+TCCG
+>Rosalind_23
+ATGTACTG
+>Rosalind_44
+ATGTCTG"
+                )
+                .unwrap()
+            ),
+            "-18
+ATAT-CCG
+---T-CCG
+ATGTACTG
+ATGT-CTG"
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use crate::utils::digraph::Graph;
+use crate::utils::shortest_path::negative_cycle;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    Graph::from_edge_lists(input, true)
+        .unwrap()
+        .iter()
+        .map(|graph| {
+            if negative_cycle(graph).is_some() {
+                "1"
+            } else {
+                "-1"
+            }
+        })
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "2
+
+4 5
+1 4 4
+4 2 3
+2 3 1
+3 1 6
+2 1 -7
+
+3 4
+1 2 -8
+2 3 20
+3 1 -1
+3 2 -30"
+            ),
+            "-1 1"
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::is_semi_connected;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    Graph::from_edge_lists(input, true)
+        .unwrap()
+        .iter()
+        .map(|graph| if is_semi_connected(graph) { "1" } else { "-1" })
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "2
+
+3 2
+3 2
+2 1
+
+3 2
+3 2
+1 2"
+            ),
+            "1 -1"
+        )
+    }
+}
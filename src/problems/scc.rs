@@ -0,0 +1,29 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::strongly_connected_components;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    strongly_connected_components(&graph).len().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "6 7
+4 1
+1 2
+2 4
+5 6
+3 2
+5 3
+3 5"
+            ),
+            "3"
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::sequence::Sequence;
+use crate::utils::subsequence::shortest_common_supersequence;
+
+pub fn solve(input: &str) -> String {
+    let mut lines = input.lines();
+
+    let dna1 = Dna::try_from(lines.next().unwrap()).unwrap();
+    let dna2 = Dna::try_from(lines.next().unwrap()).unwrap();
+
+    let merged = shortest_common_supersequence(dna1.symbols(), dna2.symbols());
+
+    format!("{}", Dna::from(merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "ATCTGAT
+TGCATA"
+            ),
+            "ATGCATGAT"
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::digraph::Graph;
+use crate::utils::shortest_path::dag_shortest_paths;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    dag_shortest_paths(&graph, 0)
+        .expect("Graph has a cycle")
+        .distances()
+        .iter()
+        .map(|distance| distance.map_or("x".to_string(), |d| d.to_string()))
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "5 6
+2 3 4
+4 3 -2
+1 4 1
+1 5 -3
+2 4 -2
+5 4 1"
+            ),
+            "0 x -4 -2 -3"
+        )
+    }
+}
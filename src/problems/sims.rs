@@ -0,0 +1,44 @@
+use crate::utils::alignment::scoring::MatchMismatch;
+use crate::utils::alignment::{Aligner, Mode};
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+
+pub fn solve(input: Fasta<Dna>) -> String {
+    let mut sequences = input.iter().map(|(_, sequence)| sequence);
+    let s = sequences.next().unwrap();
+    let t = sequences.next().unwrap();
+
+    let aligner = Aligner::new(MatchMismatch::new(1, -1), 1);
+    let alignment = aligner.align(s, t, Mode::Fitting);
+
+    format!(
+        "{}\n{}\n{}",
+        alignment.score(),
+        alignment.first(),
+        alignment.second()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_54
+GCAAACCATAAGCCCTACGTGCCGCCTGTTTAAACTCGCGAACTGAATCTTCTGCTTCACGGTGAAAGTACCACAATGGTATCACACCCCAAGGAAAC
+>Rosalind_46
+GCCGTCAGGCTGGTGTCCG"
+                )
+                .unwrap()
+            ),
+            "5
+ACCATAAGCCCTACGTG-CCG
+GCCGTCAG-GCT-GGTGTCCG"
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use crate::utils::alignment::scoring::MatchMismatch;
+use crate::utils::alignment::{Aligner, Mode};
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+
+pub fn solve(input: Fasta<Dna>) -> String {
+    let mut sequences = input.iter().map(|(_, sequence)| sequence);
+    let s = sequences.next().unwrap();
+    let t = sequences.next().unwrap();
+
+    let aligner = Aligner::new(MatchMismatch::new(1, -1), 1);
+    let alignment = aligner.align(s, t, Mode::Semiglobal);
+
+    // Write out the unaligned ends of each sequence against free end gaps
+    let (s, t) = (s.to_string(), t.to_string());
+    let (s_range, t_range) = (alignment.first_range(), alignment.second_range());
+    let gaps = |n| "-".repeat(n);
+    let first = format!(
+        "{}{}{}{}{}",
+        gaps(t_range.start),
+        &s[..s_range.start],
+        alignment.first(),
+        &s[s_range.end..],
+        gaps(t.len() - t_range.end)
+    );
+    let second = format!(
+        "{}{}{}{}{}",
+        &t[..t_range.start],
+        gaps(s_range.start),
+        alignment.second(),
+        gaps(s.len() - s_range.end),
+        &t[t_range.end..]
+    );
+
+    format!("{}\n{}\n{}", alignment.score(), first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_79
+CAGCACTTGGATTCTCGG
+>Rosalind_98
+CAGCGTGG"
+                )
+                .unwrap()
+            ),
+            "4
+CAGCACTTGGATTCTCGG
+CAG--CGTGG--------"
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use crate::utils::dna::Dna;
+use crate::utils::fasta::Fasta;
+use crate::utils::sequence::Sequence;
+use crate::utils::subsequence::subsequence_indices;
+use itertools::Itertools;
+
+pub fn solve(input: Fasta<Dna>) -> String {
+    let mut dnas = input.iter().map(|(_, dna)| dna);
+    let dna = dnas.next().unwrap();
+    let motif = dnas.next().unwrap();
+
+    subsequence_indices(dna.symbols(), motif.symbols())
+        .unwrap()
+        .iter()
+        .map(|i| i + 1)
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                Fasta::try_from(
+                    ">Rosalind_14
+ACGTACGTGACG
+>Rosalind_18
+GTA"
+                )
+                .unwrap()
+            ),
+            "3 4 5"
+        )
+    }
+}
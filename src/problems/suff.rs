@@ -0,0 +1,36 @@
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::suffix_tree::SuffixTree;
+
+pub fn solve(input: &str) -> String {
+    let dna = Dna::try_from(input.trim_end_matches('$')).unwrap();
+
+    let tree = SuffixTree::new(&dna);
+
+    tree.edge_labels().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve("ATAAATG$"),
+            "$
+A
+A
+ATG$
+TG$
+T
+AAATG$
+G$
+G$
+T
+AAATG$
+G$"
+        )
+    }
+}
@@ -0,0 +1,42 @@
+use std::convert::TryFrom;
+
+use crate::utils::dna::Dna;
+use crate::utils::trie::Trie;
+
+pub fn solve(input: &str) -> String {
+    let patterns: Vec<Dna> = input.lines().map(|l| Dna::try_from(l).unwrap()).collect();
+
+    let trie = Trie::from_patterns(&patterns);
+
+    let mut output = String::new();
+    for (parent, child, base) in trie.adjacency_list() {
+        // Rosalind numbers the nodes from 1
+        output.push_str(&format!("{} {} {}\n", parent + 1, child + 1, base))
+    }
+    output.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "ATAGA
+ATC
+GAT"
+            ),
+            "1 2 A
+2 3 T
+3 4 A
+4 5 G
+5 6 A
+3 7 C
+1 8 G
+8 9 A
+9 10 T"
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::topological_sort;
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> String {
+    let graph = Graph::from_edge_list(input, true).unwrap();
+    topological_sort(&graph)
+        .expect("Graph has a cycle")
+        .iter()
+        .map(|node| node + 1)
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "4 5
+1 2
+3 1
+3 2
+4 3
+4 2"
+            ),
+            "4 3 1 2"
+        )
+    }
+}
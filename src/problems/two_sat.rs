@@ -0,0 +1,69 @@
+use crate::utils::satisfiability::two_sat;
+use itertools::Itertools;
+
+fn parse_formula(lines: &[&str]) -> (usize, Vec<(i64, i64)>) {
+    let numbers = |line: &str| -> Vec<i64> {
+        line.split_whitespace()
+            .map(|x| x.parse().unwrap())
+            .collect()
+    };
+    let variables = numbers(lines[0])[0] as usize;
+    let clauses = lines[1..]
+        .iter()
+        .map(|line| {
+            let literals = numbers(line);
+            (literals[0], literals[1])
+        })
+        .collect();
+    (variables, clauses)
+}
+
+pub fn solve(input: &str) -> String {
+    let lines: Vec<&str> = input.lines().map(str::trim).skip(1).collect();
+    lines
+        .split(|line| line.is_empty())
+        .filter(|formula| !formula.is_empty())
+        .map(|formula| {
+            let (variables, clauses) = parse_formula(formula);
+            match two_sat(variables, &clauses).unwrap() {
+                Some(assignment) => format!(
+                    "1 {}",
+                    assignment
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &value)| if value { i as i64 + 1 } else { -(i as i64 + 1) })
+                        .join(" ")
+                ),
+                None => "0".to_string(),
+            }
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            solve(
+                "2
+
+2 4
+1 2
+-1 2
+1 -2
+-1 -2
+
+3 4
+1 2
+2 3
+-1 -2
+-2 -3"
+            ),
+            "0
+1 -1 2 -3"
+        )
+    }
+}
@@ -0,0 +1,268 @@
+use super::scoring::Scoring;
+use super::{best, Aligner, Alignment, Operation, State, UNREACHABLE};
+use crate::utils::sequence::Sequence;
+use std::fmt::Display;
+
+/// The diagonal, deletion and insertion scores of one cell
+type Scores = (i32, i32, i32);
+
+/// Blocks of rows are split in half until their traceback fits in this many
+/// cells
+const BLOCK_CELLS: usize = 1 << 16;
+
+impl<F> Aligner<F> {
+    /// Global alignment by checkpointing rows of scores rather than storing
+    /// the whole traceback, giving exactly the alignment that `global` does.
+    /// The rows of `a` are split in half, and the scores of the middle row are
+    /// kept while the traceback passes through the lower half, until a block
+    /// of rows has a traceback small enough to store. For `n` rows and `m`
+    /// columns this holds O(m log n) scores and one bounded block of
+    /// traceback, and takes O(nm log n) time, as each row is recomputed once
+    /// for every level of splitting.
+    pub fn checkpointed<S>(&self, a: &S, b: &S) -> Alignment
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.checkpointed_symbols(a.symbols(), b.symbols())
+    }
+
+    pub fn checkpointed_symbols<T>(&self, a: &[T], b: &[T]) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        self.checkpointed_in_blocks(a, b, BLOCK_CELLS)
+    }
+
+    fn checkpointed_in_blocks<T>(&self, a: &[T], b: &[T], block_cells: usize) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        let first = self.first_row(b);
+        let last = a.iter().fold(first.clone(), |row, &symbol| {
+            self.next_row(symbol, b, &row, None)
+        });
+        let (m, x, y) = last[b.len()];
+        let (score, state) = best(m, x, y);
+
+        let mut operations = Vec::with_capacity(a.len() + b.len());
+        let (j, state) =
+            self.trace_rows(a, b, &first, (b.len(), state), block_cells, &mut operations);
+        // The first row can only be reached by insertions from its start
+        if state == State::Insertion {
+            operations.extend((0..j).map(|_| Operation::Insertion));
+        }
+        operations.reverse();
+        Alignment::from_operations(a, b, operations, score)
+    }
+
+    /// The scores of the first row of a global alignment, as in `fill`
+    fn first_row<T>(&self, b: &[T]) -> Vec<Scores> {
+        let mut row = vec![(0, UNREACHABLE, UNREACHABLE)];
+        for j in 1..=b.len() {
+            let (m, x, y) = row[j - 1];
+            let (insertion, _) = best(m - self.gap_open, x - self.gap_open, y - self.gap_extend);
+            row.push((UNREACHABLE, UNREACHABLE, insertion));
+        }
+        row
+    }
+
+    /// The scores of the row aligning `symbol` against `b`, from the row before
+    /// it, with each cell's traceback byte written to `pointers` if given.
+    /// The states are compared in the same order as in `fill`, so that ties
+    /// are broken the same way.
+    fn next_row<T>(
+        &self,
+        symbol: T,
+        b: &[T],
+        previous: &[Scores],
+        mut pointers: Option<&mut [u8]>,
+    ) -> Vec<Scores>
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let (open, extend) = (self.gap_open, self.gap_extend);
+        let mut current: Vec<Scores> = Vec::with_capacity(b.len() + 1);
+        for j in 0..=b.len() {
+            let mut pointer = 0;
+            let diagonal = if j == 0 {
+                UNREACHABLE
+            } else {
+                let (m, x, y) = previous[j - 1];
+                let (score, from) = best(m, x, y);
+                pointer |= from as u8;
+                score + self.scoring.score(symbol, b[j - 1])
+            };
+            let (m, x, y) = previous[j];
+            let (deletion, from) = best(m - open, x - extend, y - open);
+            pointer |= (from as u8) << 2;
+            let insertion = if j == 0 {
+                UNREACHABLE
+            } else {
+                let (m, x, y) = current[j - 1];
+                let (score, from) = best(m - open, x - open, y - extend);
+                pointer |= (from as u8) << 4;
+                score
+            };
+            current.push((diagonal, deletion, insertion));
+            if let Some(pointers) = pointers.as_mut() {
+                pointers[j] = pointer;
+            }
+        }
+        current
+    }
+
+    /// Trace back through the rows aligning `a`, given the scores of the row
+    /// before them, from `state` in column `j` of the last row. Appends the
+    /// operations in reverse, and returns the column and state where the
+    /// traceback leaves for the row before.
+    fn trace_rows<T>(
+        &self,
+        a: &[T],
+        b: &[T],
+        first: &[Scores],
+        (mut j, mut state): (usize, State),
+        block_cells: usize,
+        operations: &mut Vec<Operation>,
+    ) -> (usize, State)
+    where
+        T: Copy + Eq,
+        F: Scoring<T>,
+    {
+        let width = b.len() + 1;
+        if a.len() > 1 && a.len() * width > block_cells {
+            let middle = a.len() / 2;
+            let exit = {
+                let row = a[..middle].iter().fold(first.to_vec(), |row, &symbol| {
+                    self.next_row(symbol, b, &row, None)
+                });
+                self.trace_rows(&a[middle..], b, &row, (j, state), block_cells, operations)
+            };
+            return self.trace_rows(&a[..middle], b, first, exit, block_cells, operations);
+        }
+
+        let mut pointers = vec![0u8; a.len() * width];
+        let mut row = first.to_vec();
+        for (&symbol, block) in a.iter().zip(pointers.chunks_mut(width)) {
+            row = self.next_row(symbol, b, &row, Some(block));
+        }
+        let mut i = a.len();
+        // A diagonal state in the first column is where `fill` stops too
+        while i > 0 && !(state == State::Diagonal && j == 0) {
+            let pointer = pointers[(i - 1) * width + j];
+            match state {
+                State::Diagonal => {
+                    operations.push(if a[i - 1] == b[j - 1] {
+                        Operation::Match
+                    } else {
+                        Operation::Mismatch
+                    });
+                    state = State::from_bits(pointer);
+                    i -= 1;
+                    j -= 1;
+                }
+                State::Deletion => {
+                    operations.push(Operation::Deletion);
+                    state = State::from_bits(pointer >> 2);
+                    i -= 1;
+                }
+                State::Insertion => {
+                    operations.push(Operation::Insertion);
+                    state = State::from_bits(pointer >> 4);
+                    j -= 1;
+                }
+                State::Start => unreachable!(),
+            }
+        }
+        (j, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::alignment::scoring::{MatchMismatch, SubstitutionMatrix};
+    use crate::utils::dna::{Dna, DnaBase};
+    use crate::utils::protein::AminoAcid;
+    use crate::utils::sequence::Alphabet;
+    use std::convert::TryFrom;
+
+    /// Deterministic pseudo-random sequences, so failures are reproducible
+    fn random_symbols<T: Alphabet>(seed: &mut u64, length: usize) -> Vec<T> {
+        (0..length)
+            .map(|_| {
+                *seed = seed
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                T::SYMBOLS[(*seed >> 33) as usize % T::SYMBOLS.len()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checkpointing_matches_full_dynamic_programming() {
+        let mut seed = 1;
+        let aligners = [
+            Aligner::new(MatchMismatch::new(1, -1), 1),
+            Aligner::new(MatchMismatch::new(1, -1), 2),
+            Aligner::affine(MatchMismatch::new(2, -3), 5, 2),
+            Aligner::affine(MatchMismatch::new(1, 0), 1, 0),
+        ];
+        let proteins = Aligner::affine(SubstitutionMatrix::blosum62(), 11, 1);
+        for round in 0..100 {
+            let a: Vec<DnaBase> = random_symbols(&mut seed, round % 30);
+            let b: Vec<DnaBase> = random_symbols(&mut seed, round * 7 % 30);
+            for aligner in &aligners {
+                let full = aligner.global_symbols(&a, &b);
+                assert_eq!(aligner.checkpointed_symbols(&a, &b), full);
+                // Split down to single rows, as for long sequences
+                assert_eq!(aligner.checkpointed_in_blocks(&a, &b, 0), full);
+            }
+            let a: Vec<AminoAcid> = random_symbols(&mut seed, round * 3 % 40);
+            let b: Vec<AminoAcid> = random_symbols(&mut seed, round * 11 % 40);
+            let (a, b): (Vec<_>, Vec<_>) = (
+                a.into_iter().filter(|&aa| aa != AminoAcid::Stop).collect(),
+                b.into_iter().filter(|&aa| aa != AminoAcid::Stop).collect(),
+            );
+            let full = proteins.global_symbols(&a, &b);
+            assert_eq!(proteins.checkpointed_symbols(&a, &b), full);
+            assert_eq!(
+                proteins.checkpointed_in_blocks(&a, &b, 2 * (b.len() + 1)),
+                full
+            );
+        }
+    }
+
+    #[test]
+    fn checkpointing_finds_unique_optimal_alignment() {
+        let aligner = Aligner::affine(MatchMismatch::new(2, -2), 5, 1);
+        let a = Dna::try_from("ACGTTTTACG").unwrap();
+        let b = Dna::try_from("ACGACG").unwrap();
+        assert_eq!(aligner.checkpointed(&a, &b), aligner.global(&a, &b))
+    }
+
+    #[test]
+    fn wide_band_matches_full_dynamic_programming() {
+        let mut seed = 7;
+        let aligner = Aligner::affine(MatchMismatch::new(2, -3), 5, 2);
+        for _ in 0..20 {
+            let a: Vec<DnaBase> = random_symbols(&mut seed, 40);
+            let mut b = a.clone();
+            b.remove(10);
+            b[25] = DnaBase::complement(b[25]);
+            b.insert(30, DnaBase::G);
+            assert_eq!(
+                aligner.banded_symbols(&a, &b, 2),
+                aligner.global_symbols(&a, &b)
+            );
+            let c: Vec<DnaBase> = random_symbols(&mut seed, 25);
+            assert_eq!(
+                aligner.banded_symbols(&a, &c, 40),
+                aligner.global_symbols(&a, &c)
+            );
+        }
+    }
+}
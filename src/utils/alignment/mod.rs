@@ -0,0 +1,549 @@
+use crate::utils::sequence::Sequence;
+use std::fmt::Display;
+use std::ops::Range;
+
+mod checkpoint;
+pub mod multiple;
+pub mod optimal;
+pub mod scoring;
+pub mod striped;
+
+use scoring::Scoring;
+
+/// A single column of a pairwise alignment, relative to the first sequence
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Operation {
+    Match,
+    Mismatch,
+    /// A symbol of the second sequence aligned to a gap in the first
+    Insertion,
+    /// A symbol of the first sequence aligned to a gap in the second
+    Deletion,
+}
+
+impl Operation {
+    fn cigar_code(self) -> char {
+        match self {
+            Operation::Match => '=',
+            Operation::Mismatch => 'X',
+            Operation::Insertion => 'I',
+            Operation::Deletion => 'D',
+        }
+    }
+}
+
+/// A pairwise alignment, with both sequences written out using `-` for gaps
+#[derive(Debug, PartialEq, Clone)]
+pub struct Alignment {
+    score: i32,
+    operations: Vec<Operation>,
+    first: String,
+    second: String,
+    first_range: Range<usize>,
+    second_range: Range<usize>,
+}
+
+impl Alignment {
+    /// Build an alignment by applying `operations` to `a` and `b`
+    pub fn from_operations<T: Display>(
+        a: &[T],
+        b: &[T],
+        operations: Vec<Operation>,
+        score: i32,
+    ) -> Self {
+        let mut first = String::new();
+        let mut second = String::new();
+        let (mut i, mut j) = (0, 0);
+        for operation in &operations {
+            match operation {
+                Operation::Match | Operation::Mismatch => {
+                    first.push_str(&a[i].to_string());
+                    second.push_str(&b[j].to_string());
+                    i += 1;
+                    j += 1;
+                }
+                Operation::Insertion => {
+                    first.push('-');
+                    second.push_str(&b[j].to_string());
+                    j += 1;
+                }
+                Operation::Deletion => {
+                    first.push_str(&a[i].to_string());
+                    second.push('-');
+                    i += 1;
+                }
+            }
+        }
+        Alignment {
+            score,
+            operations,
+            first,
+            second,
+            first_range: 0..i,
+            second_range: 0..j,
+        }
+    }
+
+    /// Shift the aligned ranges for an alignment of subslices starting at
+    /// `first_start` and `second_start`
+    fn offset(mut self, first_start: usize, second_start: usize) -> Self {
+        self.first_range = first_start + self.first_range.start..first_start + self.first_range.end;
+        self.second_range =
+            second_start + self.second_range.start..second_start + self.second_range.end;
+        self
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// The first sequence with gaps inserted
+    pub fn first(&self) -> &str {
+        &self.first
+    }
+
+    /// The second sequence with gaps inserted
+    pub fn second(&self) -> &str {
+        &self.second
+    }
+
+    /// The part of the first sequence covered by the alignment
+    pub fn first_range(&self) -> Range<usize> {
+        self.first_range.clone()
+    }
+
+    /// The part of the second sequence covered by the alignment
+    pub fn second_range(&self) -> Range<usize> {
+        self.second_range.clone()
+    }
+
+    /// The alignment as an extended CIGAR string, using `=` and `X` for
+    /// matches and mismatches
+    pub fn cigar(&self) -> String {
+        let mut cigar = String::new();
+        let mut operations = self.operations.iter().peekable();
+        while let Some(&operation) = operations.next() {
+            let mut length = 1;
+            while operations.peek() == Some(&&operation) {
+                operations.next();
+                length += 1;
+            }
+            cigar.push_str(&format!("{}{}", length, operation.cigar_code()));
+        }
+        cigar
+    }
+}
+
+/// The Levenshtein distance between two sequences: the fewest substitutions,
+/// insertions and deletions turning one into the other
+pub fn edit_distance<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(x != y))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Which parts of each sequence an alignment must cover
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Mode {
+    /// The whole of both sequences (Needleman-Wunsch)
+    Global,
+    /// The best scoring pair of substrings (Smith-Waterman)
+    Local,
+    /// The whole of both sequences, without charging for gaps at either end
+    Semiglobal,
+    /// The whole of the second sequence against a substring of the first
+    Fitting,
+    /// A suffix of the first sequence against a prefix of the second
+    Overlap,
+}
+
+impl Mode {
+    /// Whether the alignment may skip a prefix of the first sequence
+    fn skips_first_prefix(self) -> bool {
+        self != Mode::Global
+    }
+
+    /// Whether the alignment may skip a prefix of the second sequence
+    fn skips_second_prefix(self) -> bool {
+        self == Mode::Local || self == Mode::Semiglobal
+    }
+
+    /// Whether the alignment may skip a suffix of the first sequence
+    fn skips_first_suffix(self) -> bool {
+        self == Mode::Semiglobal || self == Mode::Fitting
+    }
+
+    /// Whether the alignment may skip a suffix of the second sequence
+    fn skips_second_suffix(self) -> bool {
+        self == Mode::Semiglobal || self == Mode::Overlap
+    }
+}
+
+/// Score used for alignment states that cannot be reached, low enough that
+/// subtracting gap penalties cannot overflow
+const UNREACHABLE: i32 = i32::MIN / 4;
+
+/// The last column of a partial alignment in the Gotoh recurrence
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum State {
+    Diagonal,
+    Deletion,
+    Insertion,
+    /// The start of a local alignment
+    Start,
+}
+
+impl State {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => State::Diagonal,
+            1 => State::Deletion,
+            2 => State::Insertion,
+            _ => State::Start,
+        }
+    }
+}
+
+/// The best of the three states, preferring them in the order given on ties
+fn best(diagonal: i32, deletion: i32, insertion: i32) -> (i32, State) {
+    if diagonal >= deletion && diagonal >= insertion {
+        (diagonal, State::Diagonal)
+    } else if deletion >= insertion {
+        (deletion, State::Deletion)
+    } else {
+        (insertion, State::Insertion)
+    }
+}
+
+/// Pairwise aligner scoring aligned symbols with `scoring`. A gap of length
+/// `n` costs `gap_open + (n - 1) * gap_extend`.
+pub struct Aligner<F> {
+    scoring: F,
+    gap_open: i32,
+    gap_extend: i32,
+}
+
+impl<F> Aligner<F> {
+    /// An aligner charging `gap` for every gap position
+    pub fn new(scoring: F, gap: i32) -> Self {
+        Self::affine(scoring, gap, gap)
+    }
+
+    /// An aligner with affine gap penalties, charging `open` for the first
+    /// position of each gap and `extend` for each further position
+    pub fn affine(scoring: F, open: i32, extend: i32) -> Self {
+        Self {
+            scoring,
+            gap_open: open,
+            gap_extend: extend,
+        }
+    }
+
+    /// Align the two sequences in the given mode. The alignment holds just the
+    /// aligned parts of each sequence, which are given by its ranges.
+    pub fn align<S>(&self, a: &S, b: &S, mode: Mode) -> Alignment
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.align_symbols(a.symbols(), b.symbols(), mode)
+    }
+
+    pub fn global<S>(&self, a: &S, b: &S) -> Alignment
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.align(a, b, Mode::Global)
+    }
+
+    pub fn global_symbols<T>(&self, a: &[T], b: &[T]) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        self.align_symbols(a, b, Mode::Global)
+    }
+
+    pub fn local<S>(&self, a: &S, b: &S) -> Alignment
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.align(a, b, Mode::Local)
+    }
+
+    pub fn local_symbols<T>(&self, a: &[T], b: &[T]) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        self.align_symbols(a, b, Mode::Local)
+    }
+
+    pub fn align_symbols<T>(&self, a: &[T], b: &[T], mode: Mode) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        self.fill(a, b, mode, None)
+    }
+
+    /// Global alignment only considering cells within `band` diagonals of
+    /// the corner to corner diagonals, for closely related sequences. The
+    /// result is optimal provided some optimal alignment stays in the band.
+    pub fn banded<S>(&self, a: &S, b: &S, band: usize) -> Alignment
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.banded_symbols(a.symbols(), b.symbols(), band)
+    }
+
+    pub fn banded_symbols<T>(&self, a: &[T], b: &[T], band: usize) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        self.fill(a, b, Mode::Global, Some(band))
+    }
+
+    /// Gotoh's three state recurrence, keeping only two rows of scores and a
+    /// byte of traceback per cell: two bits for each state's predecessor.
+    /// Given a band width, only cells within that many diagonals of the
+    /// corner to corner diagonals are filled.
+    fn fill<T>(&self, a: &[T], b: &[T], mode: Mode, band: Option<usize>) -> Alignment
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        let (open, extend) = (self.gap_open, self.gap_extend);
+        let local = mode == Mode::Local;
+        let (n, m) = (a.len() as isize, b.len() as isize);
+        let columns = |i: usize| match band {
+            Some(band) => {
+                let (i, band) = (i as isize, band as isize);
+                let low = i + (m - n).min(0) - band;
+                let high = i + (m - n).max(0) + band;
+                (low.max(0) as usize, high.min(m) as usize)
+            }
+            None => (0, b.len()),
+        };
+        let mut row_offsets = Vec::with_capacity(a.len() + 1);
+        let mut cells = 0;
+        for i in 0..=a.len() {
+            let (low, high) = columns(i);
+            row_offsets.push(cells);
+            cells += high + 1 - low;
+        }
+        let mut pointers = vec![0u8; cells];
+        let pointer_index = |i: usize, j: usize| row_offsets[i] + j - columns(i).0;
+
+        let mut previous = vec![(UNREACHABLE, UNREACHABLE, UNREACHABLE); b.len() + 1];
+        let mut current = previous.clone();
+        // The best place to finish the alignment: score, state, i, j
+        let mut end: Option<(i32, State, usize, usize)> = None;
+        for i in 0..=a.len() {
+            let (low, high) = columns(i);
+            // Cells just outside the band may hold scores from earlier rows
+            if low > 0 {
+                current[low - 1] = (UNREACHABLE, UNREACHABLE, UNREACHABLE);
+            }
+            if high < b.len() {
+                current[high + 1] = (UNREACHABLE, UNREACHABLE, UNREACHABLE);
+            }
+            for j in low..=high {
+                let mut pointer = 0;
+                let starts_here = match (i, j) {
+                    (0, 0) => true,
+                    (_, 0) => mode.skips_first_prefix(),
+                    (0, _) => mode.skips_second_prefix(),
+                    _ => false,
+                };
+                let diagonal = if starts_here {
+                    0
+                } else if i == 0 || j == 0 {
+                    UNREACHABLE
+                } else {
+                    let (m, x, y) = previous[j - 1];
+                    let (mut score, mut from) = best(m, x, y);
+                    if local && score <= 0 {
+                        score = 0;
+                        from = State::Start;
+                    }
+                    pointer |= from as u8;
+                    score + self.scoring.score(a[i - 1], b[j - 1])
+                };
+                let deletion = if i == 0 {
+                    UNREACHABLE
+                } else {
+                    let (m, x, y) = previous[j];
+                    let (score, from) = best(m - open, x - extend, y - open);
+                    pointer |= (from as u8) << 2;
+                    score
+                };
+                let insertion = if j == 0 {
+                    UNREACHABLE
+                } else {
+                    let (m, x, y) = current[j - 1];
+                    let (score, from) = best(m - open, x - open, y - extend);
+                    pointer |= (from as u8) << 4;
+                    score
+                };
+                current[j] = (diagonal, deletion, insertion);
+                pointers[pointer_index(i, j)] = pointer;
+
+                let candidate = if local {
+                    Some((diagonal, State::Diagonal))
+                } else if (i == a.len() && j == b.len())
+                    || (i == a.len() && mode.skips_second_suffix())
+                    || (j == b.len() && mode.skips_first_suffix())
+                {
+                    Some(best(diagonal, deletion, insertion))
+                } else {
+                    None
+                };
+                if let Some((score, state)) = candidate {
+                    if end.map_or(true, |(best, ..)| score > best) {
+                        end = Some((score, state, i, j));
+                    }
+                }
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+
+        let (score, mut state, end_i, end_j) = end.unwrap();
+        if local && score <= 0 {
+            return Alignment::from_operations(a, b, Vec::new(), 0);
+        }
+
+        let (mut i, mut j) = (end_i, end_j);
+        let mut operations = Vec::with_capacity(a.len() + b.len());
+        // Diagonal states on the edge of the matrix are where the alignment starts
+        while state != State::Start && !(state == State::Diagonal && (i == 0 || j == 0)) {
+            let pointer = pointers[pointer_index(i, j)];
+            match state {
+                State::Diagonal => {
+                    operations.push(if a[i - 1] == b[j - 1] {
+                        Operation::Match
+                    } else {
+                        Operation::Mismatch
+                    });
+                    state = State::from_bits(pointer);
+                    i -= 1;
+                    j -= 1;
+                }
+                State::Deletion => {
+                    operations.push(Operation::Deletion);
+                    state = State::from_bits(pointer >> 2);
+                    i -= 1;
+                }
+                State::Insertion => {
+                    operations.push(Operation::Insertion);
+                    state = State::from_bits(pointer >> 4);
+                    j -= 1;
+                }
+                State::Start => unreachable!(),
+            }
+        }
+        operations.reverse();
+
+        Alignment::from_operations(&a[i..end_i], &b[j..end_j], operations, score).offset(i, j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::alignment::scoring::MatchMismatch;
+    use crate::utils::dna::Dna;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn edit_distance_counts_all_edit_types() {
+        assert_eq!(edit_distance(b"PLEASANTLY", b"MEANLY"), 5);
+        assert_eq!(edit_distance(b"", b"ACGT"), 4);
+        assert_eq!(edit_distance(b"KITTEN", b"SITTING"), 3)
+    }
+
+    #[test]
+    fn global_alignment_with_traceback() {
+        let aligner = Aligner::new(MatchMismatch::new(1, -1), 2);
+        let alignment = aligner.global(
+            &Dna::try_from("GATTACA").unwrap(),
+            &Dna::try_from("GCATGCT").unwrap(),
+        );
+        assert_eq!(alignment.score(), -1);
+        assert_eq!(alignment.first(), "GATTACA");
+        assert_eq!(alignment.second(), "GCATGCT");
+        assert_eq!(alignment.cigar(), "1=2X1=1X1=1X")
+    }
+
+    #[test]
+    fn affine_gaps_are_kept_together() {
+        let aligner = Aligner::affine(MatchMismatch::new(2, -2), 5, 1);
+        let alignment = aligner.global(
+            &Dna::try_from("ACGTTTTACG").unwrap(),
+            &Dna::try_from("ACGACG").unwrap(),
+        );
+        assert_eq!(alignment.score(), 12 - 8);
+        assert_eq!(alignment.cigar(), "3=4D3=")
+    }
+
+    #[test]
+    fn local_alignment_finds_best_substrings() {
+        let aligner = Aligner::new(MatchMismatch::new(1, -2), 2);
+        let alignment = aligner.local(
+            &Dna::try_from("TTTTGATTACATTTT").unwrap(),
+            &Dna::try_from("CCGATTACACC").unwrap(),
+        );
+        assert_eq!(alignment.score(), 7);
+        assert_eq!(alignment.first(), "GATTACA");
+        assert_eq!(alignment.second(), "GATTACA");
+        let empty = aligner.local(
+            &Dna::try_from("AAAA").unwrap(),
+            &Dna::try_from("CCCC").unwrap(),
+        );
+        assert_eq!(empty.score(), 0);
+        assert!(empty.operations().is_empty())
+    }
+
+    #[test]
+    fn modes_skip_the_expected_ends() {
+        let aligner = Aligner::new(MatchMismatch::new(1, -1), 1);
+        let align = |a: &str, b: &str, mode| {
+            let alignment =
+                aligner.align(&Dna::try_from(a).unwrap(), &Dna::try_from(b).unwrap(), mode);
+            (
+                alignment.score(),
+                alignment.first_range(),
+                alignment.second_range(),
+            )
+        };
+        assert_eq!(
+            align("GGACGTACGG", "ACGTAC", Mode::Fitting),
+            (6, 2..8, 0..6)
+        );
+        assert_eq!(
+            align("CCCCACGT", "ACGTGGGG", Mode::Overlap),
+            (4, 4..8, 0..4)
+        );
+        assert_eq!(align("ACGTACGT", "TACG", Mode::Semiglobal), (4, 3..7, 0..4));
+        assert_eq!(align("TACG", "ACGTACGT", Mode::Semiglobal), (4, 0..4, 3..7));
+        assert_eq!(align("CCACGTCC", "GGACGTGG", Mode::Local), (4, 2..6, 2..6));
+        assert_eq!(align("ACGTACGT", "TACG", Mode::Global), (0, 0..8, 0..4))
+    }
+}
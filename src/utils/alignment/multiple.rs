@@ -0,0 +1,558 @@
+use super::scoring::Scoring;
+use super::{Aligner, Operation};
+use crate::utils::fasta::Fasta;
+use crate::utils::gapped::GappedSequence;
+use crate::utils::sequence::{Alphabet, Sequence};
+
+/// How the guide tree for progressive alignment is built
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TreeMethod {
+    Upgma,
+    NeighbourJoining,
+}
+
+/// The order in which sequences are merged into a multiple alignment
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GuideTree {
+    Leaf(usize),
+    Join(Box<GuideTree>, Box<GuideTree>),
+}
+
+impl GuideTree {
+    /// Build a tree from a distance matrix by repeatedly joining the closest
+    /// pair of clusters, averaging distances weighted by cluster size
+    pub fn upgma(distances: &[Vec<f64>]) -> Option<Self> {
+        let mut clusters: Vec<(GuideTree, usize)> = (0..distances.len())
+            .map(|i| (GuideTree::Leaf(i), 1))
+            .collect();
+        let mut distances = distances.to_vec();
+        while clusters.len() > 1 {
+            let (i, j) = closest_pair(clusters.len(), |i, j| distances[i][j]);
+            let (size_i, size_j) = (clusters[i].1 as f64, clusters[j].1 as f64);
+            let joined: Vec<f64> = (0..clusters.len())
+                .map(|k| (distances[i][k] * size_i + distances[j][k] * size_j) / (size_i + size_j))
+                .collect();
+            clusters = join_clusters(clusters, &mut distances, i, j, joined);
+        }
+        clusters.pop().map(|(tree, _)| tree)
+    }
+
+    /// Build a tree from a distance matrix by Saitou and Nei's neighbour
+    /// joining. The tree is rooted at the final join.
+    pub fn neighbour_joining(distances: &[Vec<f64>]) -> Option<Self> {
+        let mut clusters: Vec<(GuideTree, usize)> = (0..distances.len())
+            .map(|i| (GuideTree::Leaf(i), 1))
+            .collect();
+        let mut distances = distances.to_vec();
+        while clusters.len() > 1 {
+            let n = clusters.len();
+            let totals: Vec<f64> = distances.iter().map(|row| row.iter().sum()).collect();
+            let (i, j) = if n == 2 {
+                (0, 1)
+            } else {
+                closest_pair(n, |i, j| {
+                    (n as f64 - 2.0) * distances[i][j] - totals[i] - totals[j]
+                })
+            };
+            let joined: Vec<f64> = (0..n)
+                .map(|k| (distances[i][k] + distances[j][k] - distances[i][j]) / 2.0)
+                .collect();
+            clusters = join_clusters(clusters, &mut distances, i, j, joined);
+        }
+        clusters.pop().map(|(tree, _)| tree)
+    }
+
+    /// The sequences under this node, from left to right
+    pub fn leaves(&self) -> Vec<usize> {
+        match self {
+            GuideTree::Leaf(i) => vec![*i],
+            GuideTree::Join(left, right) => {
+                let mut leaves = left.leaves();
+                leaves.extend(right.leaves());
+                leaves
+            }
+        }
+    }
+}
+
+/// The pair `i < j` minimising `value`, taking the first on ties
+fn closest_pair(n: usize, value: impl Fn(usize, usize) -> f64) -> (usize, usize) {
+    let mut best = (f64::INFINITY, 0, 1);
+    for i in 0..n {
+        for j in i + 1..n {
+            let v = value(i, j);
+            if v < best.0 {
+                best = (v, i, j);
+            }
+        }
+    }
+    (best.1, best.2)
+}
+
+/// Replace clusters `i < j` by their join, whose distances to every cluster
+/// are `joined`, indexed before the join
+fn join_clusters(
+    mut clusters: Vec<(GuideTree, usize)>,
+    distances: &mut Vec<Vec<f64>>,
+    i: usize,
+    j: usize,
+    joined: Vec<f64>,
+) -> Vec<(GuideTree, usize)> {
+    let (right, right_size) = clusters.remove(j);
+    let (left, left_size) = clusters.remove(i);
+    let mut row: Vec<f64> = joined
+        .into_iter()
+        .enumerate()
+        .filter(|&(k, _)| k != i && k != j)
+        .map(|(_, d)| d)
+        .collect();
+    distances.remove(j);
+    distances.remove(i);
+    for (other, d) in distances.iter_mut().zip(&row) {
+        other.remove(j);
+        other.remove(i);
+        other.push(*d);
+    }
+    row.push(0.0);
+    distances.push(row);
+    clusters.push((
+        GuideTree::Join(Box::new(left), Box::new(right)),
+        left_size + right_size,
+    ));
+    clusters
+}
+
+/// Aligned rows, each tagged with the index of its input sequence
+type Rows<T> = Vec<(usize, Vec<Option<T>>)>;
+
+/// Multiple sequence aligner using sum-of-pairs scores with linear gap
+/// penalties: every pair of rows is scored as a pairwise alignment, with
+/// columns where both rows have a gap scoring zero.
+pub struct MultipleAligner<F> {
+    aligner: Aligner<F>,
+    method: TreeMethod,
+}
+
+impl<F> MultipleAligner<F> {
+    /// An aligner charging `gap` for every symbol aligned to a gap, merging
+    /// sequences in the order given by a guide tree built using `method`
+    pub fn new(scoring: F, gap: i32, method: TreeMethod) -> Self {
+        Self {
+            aligner: Aligner::new(scoring, gap),
+            method,
+        }
+    }
+
+    fn gap(&self) -> i32 {
+        self.aligner.gap_open
+    }
+
+    /// Distances between each pair of sequences: the fraction of columns of
+    /// their optimal global alignment which are not matches
+    pub fn distances<S>(&self, sequences: &Fasta<S>) -> Vec<Vec<f64>>
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        let sequences: Vec<&S> = sequences.iter().map(|(_, s)| s).collect();
+        let mut distances = vec![vec![0.0; sequences.len()]; sequences.len()];
+        for i in 0..sequences.len() {
+            for j in i + 1..sequences.len() {
+                let alignment = self.aligner.global(sequences[i], sequences[j]);
+                let operations = alignment.operations();
+                let matches = operations
+                    .iter()
+                    .filter(|&&operation| operation == Operation::Match)
+                    .count();
+                let distance = if operations.is_empty() {
+                    0.0
+                } else {
+                    1.0 - matches as f64 / operations.len() as f64
+                };
+                distances[i][j] = distance;
+                distances[j][i] = distance;
+            }
+        }
+        distances
+    }
+
+    /// Progressive alignment: sequences are merged following the guide tree,
+    /// aligning the profiles of the two groups at each join. Rows keep the
+    /// order and ids of the input.
+    pub fn align<S>(&self, sequences: &Fasta<S>) -> Fasta<GappedSequence<S::Symbol>>
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        let distances = self.distances(sequences);
+        let tree = match self.method {
+            TreeMethod::Upgma => GuideTree::upgma(&distances),
+            TreeMethod::NeighbourJoining => GuideTree::neighbour_joining(&distances),
+        };
+        let symbols: Vec<&[S::Symbol]> = sequences.iter().map(|(_, s)| s.symbols()).collect();
+        let mut rows = match tree {
+            Some(tree) => self.align_tree(&tree, &symbols),
+            None => Vec::new(),
+        };
+        rows.sort_by_key(|&(i, _)| i);
+        collect_rows(sequences, rows)
+    }
+
+    fn align_tree<T>(&self, tree: &GuideTree, sequences: &[&[T]]) -> Rows<T>
+    where
+        T: Alphabet,
+        F: Scoring<T>,
+    {
+        match tree {
+            GuideTree::Leaf(i) => vec![(*i, sequences[*i].iter().map(|&s| Some(s)).collect())],
+            GuideTree::Join(left, right) => {
+                let left = self.align_tree(left, sequences);
+                let right = self.align_tree(right, sequences);
+                self.align_profiles(left, right)
+            }
+        }
+    }
+
+    /// Align two groups of aligned rows, scoring each pair of columns by
+    /// the sum of the scores of every pair of rows across the groups
+    fn align_profiles<T>(&self, a: Rows<T>, b: Rows<T>) -> Rows<T>
+    where
+        T: Alphabet,
+        F: Scoring<T>,
+    {
+        let gap = self.gap();
+        let symbols = T::SYMBOLS.len();
+        let substitution: Vec<i32> = T::SYMBOLS
+            .iter()
+            .flat_map(|&x| T::SYMBOLS.iter().map(move |&y| (x, y)))
+            .map(|(x, y)| self.aligner.scoring.score(x, y))
+            .collect();
+        let a_profile = profile(&a);
+        let b_profile = profile(&b);
+        let (a_rows, b_rows) = (a.len() as i32, b.len() as i32);
+        let residues = |column: &[i32]| column[..symbols].iter().sum::<i32>();
+        // For each column of `a`, the score of its symbols against each symbol
+        let a_weighted: Vec<Vec<i32>> = a_profile
+            .iter()
+            .map(|column| {
+                (0..symbols)
+                    .map(|y| {
+                        (0..symbols)
+                            .map(|x| column[x] * substitution[x * symbols + y])
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+        let pair = |i: usize, j: usize| {
+            let (x, y) = (&a_profile[i], &b_profile[j]);
+            let symbols_score: i32 = (0..symbols).map(|k| a_weighted[i][k] * y[k]).sum();
+            symbols_score - gap * (x[symbols] * residues(y) + residues(x) * y[symbols])
+        };
+        let deletion = |i: usize| -gap * residues(&a_profile[i]) * b_rows;
+        let insertion = |j: usize| -gap * residues(&b_profile[j]) * a_rows;
+
+        let (n, m) = (a_profile.len(), b_profile.len());
+        let mut pointers = vec![Operation::Match; (n + 1) * (m + 1)];
+        let mut previous = vec![0; m + 1];
+        let mut current = vec![0; m + 1];
+        for j in 1..=m {
+            previous[j] = previous[j - 1] + insertion(j - 1);
+            pointers[j] = Operation::Insertion;
+        }
+        for i in 1..=n {
+            current[0] = previous[0] + deletion(i - 1);
+            pointers[i * (m + 1)] = Operation::Deletion;
+            for j in 1..=m {
+                let candidates = [
+                    (previous[j - 1] + pair(i - 1, j - 1), Operation::Match),
+                    (previous[j] + deletion(i - 1), Operation::Deletion),
+                    (current[j - 1] + insertion(j - 1), Operation::Insertion),
+                ];
+                let (score, operation) =
+                    candidates.iter().fold(
+                        candidates[0],
+                        |best, &c| if c.0 > best.0 { c } else { best },
+                    );
+                current[j] = score;
+                pointers[i * (m + 1) + j] = operation;
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+
+        let (mut i, mut j) = (n, m);
+        let mut operations = Vec::with_capacity(n + m);
+        while i > 0 || j > 0 {
+            let operation = pointers[i * (m + 1) + j];
+            operations.push(operation);
+            match operation {
+                Operation::Deletion => i -= 1,
+                Operation::Insertion => j -= 1,
+                _ => {
+                    i -= 1;
+                    j -= 1;
+                }
+            }
+        }
+        operations.reverse();
+
+        let merge = |rows: Rows<T>, gapped: Operation| -> Rows<T> {
+            rows.into_iter()
+                .map(|(index, row)| {
+                    let mut symbols = row.into_iter();
+                    let row = operations
+                        .iter()
+                        .map(|&operation| {
+                            if operation == gapped {
+                                None
+                            } else {
+                                symbols.next().unwrap()
+                            }
+                        })
+                        .collect();
+                    (index, row)
+                })
+                .collect()
+        };
+        let mut rows = merge(a, Operation::Insertion);
+        rows.extend(merge(b, Operation::Deletion));
+        rows
+    }
+
+    /// An optimal alignment by dynamic programming over every combination of
+    /// prefixes, which takes time exponential in the number of sequences and
+    /// so is limited to four of them
+    pub fn align_exact<S>(
+        &self,
+        sequences: &Fasta<S>,
+    ) -> Result<Fasta<GappedSequence<S::Symbol>>, String>
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        let symbols: Vec<&[S::Symbol]> = sequences.iter().map(|(_, s)| s.symbols()).collect();
+        let k = symbols.len();
+        if k > 4 {
+            return Err(format!(
+                "Exact alignment supports at most 4 sequences, got {}",
+                k
+            ));
+        }
+
+        let mut strides = vec![1; k];
+        for d in (0..k.saturating_sub(1)).rev() {
+            strides[d] = strides[d + 1] * (symbols[d + 1].len() + 1);
+        }
+        let cells: usize = symbols.iter().map(|s| s.len() + 1).product();
+        let coordinates = |cell: usize| -> Vec<usize> {
+            (0..k)
+                .map(|d| cell / strides[d] % (symbols[d].len() + 1))
+                .collect()
+        };
+
+        let mut scores = vec![0; cells];
+        let mut moves = vec![0u8; cells];
+        let full: u8 = ((1u16 << k) - 1) as u8;
+        for cell in 1..cells {
+            let position = coordinates(cell);
+            let mut best: Option<(i32, u8)> = None;
+            // Moves advance each sequence in the mask by one symbol
+            for mask in (1..=full).rev() {
+                if (0..k).any(|d| mask & (1 << d) != 0 && position[d] == 0) {
+                    continue;
+                }
+                let column: Vec<Option<S::Symbol>> = (0..k)
+                    .map(|d| {
+                        if mask & (1 << d) != 0 {
+                            Some(symbols[d][position[d] - 1])
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                let from = cell
+                    - (0..k)
+                        .filter(|&d| mask & (1 << d) != 0)
+                        .map(|d| strides[d])
+                        .sum::<usize>();
+                let score = scores[from] + self.column_score(&column);
+                if best.map_or(true, |(b, _)| score > b) {
+                    best = Some((score, mask));
+                }
+            }
+            let (score, mask) = best.unwrap();
+            scores[cell] = score;
+            moves[cell] = mask;
+        }
+
+        let mut rows: Vec<Vec<Option<S::Symbol>>> = vec![Vec::new(); k];
+        let mut cell = cells - 1;
+        while cell > 0 {
+            let position = coordinates(cell);
+            let mask = moves[cell];
+            for d in 0..k {
+                rows[d].push(if mask & (1 << d) != 0 {
+                    cell -= strides[d];
+                    Some(symbols[d][position[d] - 1])
+                } else {
+                    None
+                });
+            }
+        }
+        for row in &mut rows {
+            row.reverse();
+        }
+        Ok(collect_rows(
+            sequences,
+            rows.into_iter().enumerate().collect(),
+        ))
+    }
+
+    /// The sum-of-pairs score of one column
+    fn column_score<T>(&self, column: &[Option<T>]) -> i32
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let mut score = 0;
+        for (i, &x) in column.iter().enumerate() {
+            for &y in &column[i + 1..] {
+                score += match (x, y) {
+                    (Some(x), Some(y)) => self.aligner.scoring.score(x, y),
+                    (None, None) => 0,
+                    _ => -self.gap(),
+                }
+            }
+        }
+        score
+    }
+
+    /// The sum over every pair of rows of their pairwise alignment score.
+    /// All rows must be the same length.
+    pub fn sum_of_pairs<T>(&self, alignment: &Fasta<GappedSequence<T>>) -> i32
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let rows: Vec<&[Option<T>]> = alignment.iter().map(|(_, row)| row.symbols()).collect();
+        let length = rows.first().map_or(0, |row| row.len());
+        (0..length)
+            .map(|i| {
+                let column: Vec<Option<T>> = rows.iter().map(|row| row[i]).collect();
+                self.column_score(&column)
+            })
+            .sum()
+    }
+}
+
+/// Symbol and gap counts for each column of some aligned rows, with gaps
+/// counted after the symbols
+fn profile<T: Alphabet>(rows: &Rows<T>) -> Vec<Vec<i32>> {
+    let length = rows.first().map_or(0, |(_, row)| row.len());
+    let mut profile = vec![vec![0; T::SYMBOLS.len() + 1]; length];
+    for (_, row) in rows {
+        for (column, symbol) in profile.iter_mut().zip(row) {
+            match symbol {
+                Some(symbol) => column[symbol.index()] += 1,
+                None => column[T::SYMBOLS.len()] += 1,
+            }
+        }
+    }
+    profile
+}
+
+/// Pair rows, sorted by input index, with the ids of the input sequences
+fn collect_rows<S, T>(sequences: &Fasta<S>, rows: Rows<T>) -> Fasta<GappedSequence<T>> {
+    Fasta::from(
+        sequences
+            .iter()
+            .zip(rows)
+            .map(|((id, _), (_, row))| (id.clone(), GappedSequence::from(row)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::alignment::scoring::MatchMismatch;
+    use crate::utils::dna::Dna;
+    use std::convert::TryFrom;
+
+    fn sequences() -> Fasta<Dna> {
+        Fasta::try_from(
+            ">a
+ACGTACGTTT
+>b
+ACGTTCGTT
+>c
+TTACGAACGT
+>d
+ACGAACGT
+>e
+ACGTACGTTA",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn guide_trees_join_closest_sequences() {
+        let distances = vec![
+            vec![0.0, 2.0, 8.0, 8.0],
+            vec![2.0, 0.0, 8.0, 8.0],
+            vec![8.0, 8.0, 0.0, 4.0],
+            vec![8.0, 8.0, 4.0, 0.0],
+        ];
+        let pair =
+            |i, j| GuideTree::Join(Box::new(GuideTree::Leaf(i)), Box::new(GuideTree::Leaf(j)));
+        assert_eq!(
+            GuideTree::upgma(&distances),
+            Some(GuideTree::Join(Box::new(pair(0, 1)), Box::new(pair(2, 3))))
+        );
+        let mut leaves = GuideTree::neighbour_joining(&distances).unwrap().leaves();
+        leaves.sort_unstable();
+        assert_eq!(leaves, vec![0, 1, 2, 3]);
+        assert_eq!(GuideTree::upgma(&[]), None)
+    }
+
+    #[test]
+    fn progressive_alignment_keeps_every_sequence() {
+        let input = sequences();
+        for &method in &[TreeMethod::Upgma, TreeMethod::NeighbourJoining] {
+            let aligner = MultipleAligner::new(MatchMismatch::new(1, -1), 2, method);
+            let alignment = aligner.align(&input);
+            let length = alignment.iter().next().unwrap().1.len();
+            for ((id, row), (input_id, sequence)) in alignment.iter().zip(input.iter()) {
+                assert_eq!(id, input_id);
+                assert_eq!(row.len(), length);
+                assert_eq!(row.ungapped(), sequence.symbols());
+            }
+        }
+    }
+
+    #[test]
+    fn exact_alignment_is_at_least_as_good_as_progressive() {
+        let input: Fasta<Dna> =
+            Fasta::from(sequences().iter().take(4).cloned().collect::<Vec<_>>());
+        let aligner = MultipleAligner::new(MatchMismatch::new(0, -1), 1, TreeMethod::Upgma);
+        let exact = aligner.align_exact(&input).unwrap();
+        for ((_, row), (_, sequence)) in exact.iter().zip(input.iter()) {
+            assert_eq!(row.ungapped(), sequence.symbols());
+        }
+        assert!(aligner.sum_of_pairs(&exact) >= aligner.sum_of_pairs(&aligner.align(&input)));
+        assert!(aligner.align_exact(&sequences()).is_err());
+
+        // Two sequences reduce to pairwise global alignment
+        let pair: Fasta<Dna> = Fasta::from(sequences().iter().take(2).cloned().collect::<Vec<_>>());
+        let (a, b) = (
+            &pair.iter().next().unwrap().1,
+            &pair.iter().nth(1).unwrap().1,
+        );
+        assert_eq!(
+            aligner.sum_of_pairs(&aligner.align_exact(&pair).unwrap()),
+            Aligner::new(MatchMismatch::new(0, -1), 1)
+                .global(a, b)
+                .score()
+        )
+    }
+}
@@ -0,0 +1,338 @@
+use super::scoring::Scoring;
+use super::{best, Aligner, Alignment, Operation, State, UNREACHABLE};
+use crate::utils::sequence::Sequence;
+use std::fmt::Display;
+
+/// Full Gotoh score matrices for a global alignment, kept so that every
+/// optimal path through them can be followed
+struct Matrices {
+    width: usize,
+    diagonal: Vec<i32>,
+    deletion: Vec<i32>,
+    insertion: Vec<i32>,
+}
+
+impl Matrices {
+    fn get(&self, state: State, i: usize, j: usize) -> i32 {
+        let index = i * self.width + j;
+        match state {
+            State::Diagonal => self.diagonal[index],
+            State::Deletion => self.deletion[index],
+            State::Insertion => self.insertion[index],
+            State::Start => UNREACHABLE,
+        }
+    }
+}
+
+const STATES: [State; 3] = [State::Diagonal, State::Deletion, State::Insertion];
+
+fn add_modulo(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+impl<F> Aligner<F> {
+    fn matrices<T>(&self, a: &[T], b: &[T]) -> Matrices
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let (open, extend) = (self.gap_open, self.gap_extend);
+        let width = b.len() + 1;
+        let cells = (a.len() + 1) * width;
+        let mut matrices = Matrices {
+            width,
+            diagonal: vec![UNREACHABLE; cells],
+            deletion: vec![UNREACHABLE; cells],
+            insertion: vec![UNREACHABLE; cells],
+        };
+        matrices.diagonal[0] = 0;
+        for i in 0..=a.len() {
+            for j in 0..=b.len() {
+                let index = i * width + j;
+                if i > 0 && j > 0 {
+                    let (m, x, y) = (
+                        matrices.diagonal[index - width - 1],
+                        matrices.deletion[index - width - 1],
+                        matrices.insertion[index - width - 1],
+                    );
+                    matrices.diagonal[index] =
+                        best(m, x, y).0 + self.scoring.score(a[i - 1], b[j - 1]);
+                }
+                if i > 0 {
+                    let (m, x, y) = (
+                        matrices.diagonal[index - width],
+                        matrices.deletion[index - width],
+                        matrices.insertion[index - width],
+                    );
+                    matrices.deletion[index] = best(m - open, x - extend, y - open).0;
+                }
+                if j > 0 {
+                    let (m, x, y) = (
+                        matrices.diagonal[index - 1],
+                        matrices.deletion[index - 1],
+                        matrices.insertion[index - 1],
+                    );
+                    matrices.insertion[index] = best(m - open, x - open, y - extend).0;
+                }
+            }
+        }
+        matrices
+    }
+
+    /// The states before `state` at `(i, j)` on some optimal path to it, in
+    /// the order `global` prefers them, with the cell they are in
+    fn optimal_predecessors<T>(
+        &self,
+        a: &[T],
+        b: &[T],
+        matrices: &Matrices,
+        state: State,
+        i: usize,
+        j: usize,
+    ) -> Vec<(State, usize, usize)>
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let value = matrices.get(state, i, j);
+        let (previous_i, previous_j) = match state {
+            State::Diagonal if i > 0 && j > 0 => (i - 1, j - 1),
+            State::Deletion if i > 0 => (i - 1, j),
+            State::Insertion if j > 0 => (i, j - 1),
+            _ => return Vec::new(),
+        };
+        STATES
+            .iter()
+            .filter(|&&from| {
+                let step = match state {
+                    State::Diagonal => self.scoring.score(a[i - 1], b[j - 1]),
+                    _ if from == state => -self.gap_extend,
+                    _ => -self.gap_open,
+                };
+                let previous = matrices.get(from, previous_i, previous_j);
+                previous > UNREACHABLE && previous + step == value
+            })
+            .map(|&from| (from, previous_i, previous_j))
+            .collect()
+    }
+
+    /// The number of optimal global alignments, modulo `modulus`, which must
+    /// be positive
+    pub fn count_optimal<S>(&self, a: &S, b: &S, modulus: u64) -> u64
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.count_optimal_symbols(a.symbols(), b.symbols(), modulus)
+    }
+
+    pub fn count_optimal_symbols<T>(&self, a: &[T], b: &[T], modulus: u64) -> u64
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        assert!(modulus > 0, "The modulus must be positive");
+        let matrices = self.matrices(a, b);
+        let width = matrices.width;
+        // Optimal paths reaching each state of each cell
+        let mut counts = vec![[0u64; 3]; (a.len() + 1) * width];
+        counts[0][0] = 1 % modulus;
+        for i in 0..=a.len() {
+            for j in 0..=b.len() {
+                for (s, &state) in STATES.iter().enumerate() {
+                    for (from, fi, fj) in self.optimal_predecessors(a, b, &matrices, state, i, j) {
+                        let count = counts[fi * width + fj][from as usize];
+                        let total = &mut counts[i * width + j][s];
+                        *total = add_modulo(*total, count, modulus);
+                    }
+                }
+            }
+        }
+
+        let end = a.len() * width + b.len();
+        let score = STATES
+            .iter()
+            .map(|&state| matrices.get(state, a.len(), b.len()))
+            .max()
+            .unwrap();
+        STATES
+            .iter()
+            .filter(|&&state| matrices.get(state, a.len(), b.len()) == score)
+            .fold(0, |total, &state| {
+                add_modulo(total, counts[end][state as usize], modulus)
+            })
+    }
+
+    /// Iterate over every optimal global alignment, starting with the one
+    /// `global` returns. The full score matrices are kept, so this is only
+    /// suitable for short sequences.
+    pub fn co_optimal<'a, S>(&'a self, a: &'a S, b: &'a S) -> CoOptimal<'a, S::Symbol, F>
+    where
+        S: Sequence,
+        F: Scoring<S::Symbol>,
+    {
+        self.co_optimal_symbols(a.symbols(), b.symbols())
+    }
+
+    pub fn co_optimal_symbols<'a, T>(&'a self, a: &'a [T], b: &'a [T]) -> CoOptimal<'a, T, F>
+    where
+        T: Copy + Eq + Display,
+        F: Scoring<T>,
+    {
+        let matrices = self.matrices(a, b);
+        let score = STATES
+            .iter()
+            .map(|&state| matrices.get(state, a.len(), b.len()))
+            .max()
+            .unwrap();
+        // Push in reverse so that the preferred state is explored first
+        let stack = STATES
+            .iter()
+            .rev()
+            .filter(|&&state| matrices.get(state, a.len(), b.len()) == score)
+            .map(|&state| (state, a.len(), b.len(), Vec::new()))
+            .collect();
+        CoOptimal {
+            aligner: self,
+            a,
+            b,
+            matrices,
+            score,
+            stack,
+        }
+    }
+}
+
+/// A depth first walk over the optimal tracebacks of a global alignment
+pub struct CoOptimal<'a, T, F> {
+    aligner: &'a Aligner<F>,
+    a: &'a [T],
+    b: &'a [T],
+    matrices: Matrices,
+    score: i32,
+    stack: Vec<(State, usize, usize, Vec<Operation>)>,
+}
+
+impl<T, F> Iterator for CoOptimal<'_, T, F>
+where
+    T: Copy + Eq + Display,
+    F: Scoring<T>,
+{
+    type Item = Alignment;
+
+    fn next(&mut self) -> Option<Alignment> {
+        while let Some((state, i, j, mut operations)) = self.stack.pop() {
+            if i == 0 && j == 0 {
+                operations.reverse();
+                return Some(Alignment::from_operations(
+                    self.a, self.b, operations, self.score,
+                ));
+            }
+            operations.push(match state {
+                State::Diagonal if self.a[i - 1] == self.b[j - 1] => Operation::Match,
+                State::Diagonal => Operation::Mismatch,
+                State::Deletion => Operation::Deletion,
+                State::Insertion => Operation::Insertion,
+                State::Start => unreachable!(),
+            });
+            let predecessors =
+                self.aligner
+                    .optimal_predecessors(self.a, self.b, &self.matrices, state, i, j);
+            for (from, i, j) in predecessors.into_iter().rev() {
+                self.stack.push((from, i, j, operations.clone()));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::alignment::scoring::MatchMismatch;
+    use crate::utils::dna::Dna;
+    use std::convert::TryFrom;
+
+    /// The score of a list of operations aligning the whole of `a` and `b`
+    fn operations_score<F, T>(
+        aligner: &Aligner<F>,
+        a: &[T],
+        b: &[T],
+        operations: &[Operation],
+    ) -> i32
+    where
+        T: Copy,
+        F: Scoring<T>,
+    {
+        let (mut i, mut j) = (0, 0);
+        let mut score = 0;
+        let mut previous = None;
+        for &operation in operations {
+            match operation {
+                Operation::Match | Operation::Mismatch => {
+                    score += aligner.scoring.score(a[i], b[j]);
+                    i += 1;
+                    j += 1;
+                }
+                Operation::Deletion | Operation::Insertion => {
+                    score -= if previous == Some(operation) {
+                        aligner.gap_extend
+                    } else {
+                        aligner.gap_open
+                    };
+                    if operation == Operation::Deletion {
+                        i += 1;
+                    } else {
+                        j += 1;
+                    }
+                }
+            }
+            previous = Some(operation);
+        }
+        score
+    }
+
+    #[test]
+    fn counts_agree_with_enumeration() {
+        let edit = Aligner::new(MatchMismatch::new(0, -1), 1);
+        let affine = Aligner::affine(MatchMismatch::new(1, -1), 3, 1);
+        for (a, b) in &[
+            ("ACGT", "AGT"),
+            ("AAAA", "AA"),
+            ("GATTACA", "GCATGCT"),
+            ("", "ACG"),
+        ] {
+            let (a, b) = (Dna::try_from(*a).unwrap(), Dna::try_from(*b).unwrap());
+            for aligner in &[&edit, &affine] {
+                let alignments: Vec<Alignment> = aligner.co_optimal(&a, &b).collect();
+                assert_eq!(alignments[0], aligner.global(&a, &b));
+                assert_eq!(
+                    aligner.count_optimal(&a, &b, u64::MAX),
+                    alignments.len() as u64
+                );
+                for alignment in &alignments {
+                    assert_eq!(
+                        operations_score(
+                            aligner,
+                            a.sequence(),
+                            b.sequence(),
+                            alignment.operations()
+                        ),
+                        alignment.score()
+                    );
+                }
+            }
+        }
+        let (a, b) = (Dna::try_from("AAAA").unwrap(), Dna::try_from("AA").unwrap());
+        // Choose which two of the four bases to delete
+        assert_eq!(edit.count_optimal(&a, &b, u64::MAX), 6);
+        assert_eq!(edit.count_optimal(&a, &b, 4), 2)
+    }
+
+    #[test]
+    #[should_panic(expected = "The modulus must be positive")]
+    fn zero_modulus_is_rejected() {
+        let (a, b) = (Dna::try_from("AAAA").unwrap(), Dna::try_from("AA").unwrap());
+        Aligner::new(MatchMismatch::new(0, -1), 1).count_optimal(&a, &b, 0);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::utils::protein::AminoAcid;
+use crate::utils::sequence::Alphabet;
+use std::convert::TryFrom;
+
+/// A way of scoring a pair of aligned symbols
+pub trait Scoring<T> {
+    fn score(&self, a: T, b: T) -> i32;
+}
+
+impl<T, F: Fn(T, T) -> i32> Scoring<T> for F {
+    fn score(&self, a: T, b: T) -> i32 {
+        self(a, b)
+    }
+}
+
+/// Fixed scores for identical and differing symbols, typically used for nucleotides
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct MatchMismatch {
+    match_score: i32,
+    mismatch_score: i32,
+}
+
+impl MatchMismatch {
+    pub fn new(match_score: i32, mismatch_score: i32) -> Self {
+        Self {
+            match_score,
+            mismatch_score,
+        }
+    }
+}
+
+impl<T: Eq> Scoring<T> for MatchMismatch {
+    fn score(&self, a: T, b: T) -> i32 {
+        if a == b {
+            self.match_score
+        } else {
+            self.mismatch_score
+        }
+    }
+}
+
+const BLOSUM45: &str = include_str!("matrices/BLOSUM45");
+const BLOSUM62: &str = include_str!("matrices/BLOSUM62");
+const BLOSUM80: &str = include_str!("matrices/BLOSUM80");
+const PAM250: &str = include_str!("matrices/PAM250");
+
+const SIZE: usize = 21;
+
+/// Scores for every pair of amino acids, including the stop symbol `*`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubstitutionMatrix {
+    scores: [[i32; SIZE]; SIZE],
+}
+
+impl SubstitutionMatrix {
+    pub fn blosum45() -> Self {
+        Self::try_from(BLOSUM45).unwrap()
+    }
+
+    pub fn blosum62() -> Self {
+        Self::try_from(BLOSUM62).unwrap()
+    }
+
+    pub fn blosum80() -> Self {
+        Self::try_from(BLOSUM80).unwrap()
+    }
+
+    pub fn pam250() -> Self {
+        Self::try_from(PAM250).unwrap()
+    }
+
+    /// One of the embedded matrices, looked up by name ignoring case
+    pub fn named(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "blosum45" => Ok(Self::blosum45()),
+            "blosum62" => Ok(Self::blosum62()),
+            "blosum80" => Ok(Self::blosum80()),
+            "pam250" => Ok(Self::pam250()),
+            _ => Err(format!("Unknown substitution matrix: {}", name)),
+        }
+    }
+
+    pub fn score(&self, a: AminoAcid, b: AminoAcid) -> i32 {
+        self.scores[a.index()][b.index()]
+    }
+}
+
+impl Scoring<AminoAcid> for SubstitutionMatrix {
+    fn score(&self, a: AminoAcid, b: AminoAcid) -> i32 {
+        SubstitutionMatrix::score(self, a, b)
+    }
+}
+
+/// Parse a matrix symbol, returning `None` for ambiguity codes such as `B`, `Z` and `X`
+fn parse_symbol(symbol: &str) -> Result<Option<AminoAcid>, String> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some('*'), None) => Ok(Some(AminoAcid::Stop)),
+        (Some(c), None) if c.is_ascii_alphabetic() => Ok(AminoAcid::try_from(c).ok()),
+        _ => Err(format!("Invalid matrix symbol: {}", symbol)),
+    }
+}
+
+/// Parse a matrix in the NCBI text format: `#` comments, a header row of
+/// symbols, then one row per symbol starting with that symbol
+impl TryFrom<&str> for SubstitutionMatrix {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let columns = lines
+            .next()
+            .ok_or_else(|| "Missing matrix header".to_string())?
+            .split_whitespace()
+            .map(parse_symbol)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut scores = [[0; SIZE]; SIZE];
+        let mut seen = [[false; SIZE]; SIZE];
+        for line in lines {
+            let mut fields = line.split_whitespace();
+            let row = parse_symbol(fields.next().unwrap())?;
+            let values: Vec<&str> = fields.collect();
+            if values.len() != columns.len() {
+                return Err(format!("Wrong number of scores in row: {}", line));
+            }
+            let row = match row {
+                Some(row) => row,
+                None => continue,
+            };
+            for (column, value) in columns.iter().zip(values) {
+                if let Some(column) = column {
+                    scores[row.index()][column.index()] = value
+                        .parse()
+                        .map_err(|_| format!("Invalid score: {}", value))?;
+                    seen[row.index()][column.index()] = true;
+                }
+            }
+        }
+
+        // The stop symbol is optional, but every amino acid pair must be scored
+        let required = AminoAcid::SYMBOLS
+            .iter()
+            .filter(|&&aa| aa != AminoAcid::Stop)
+            .map(|aa| aa.index());
+        for i in required.clone() {
+            for j in required.clone() {
+                if !seen[i][j] {
+                    return Err(format!(
+                        "Missing score for {} and {}",
+                        AminoAcid::SYMBOLS[i],
+                        AminoAcid::SYMBOLS[j]
+                    ));
+                }
+            }
+        }
+
+        Ok(SubstitutionMatrix { scores })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_matrices_are_symmetric() {
+        for matrix in &[
+            SubstitutionMatrix::blosum45(),
+            SubstitutionMatrix::blosum62(),
+            SubstitutionMatrix::blosum80(),
+            SubstitutionMatrix::pam250(),
+        ] {
+            for &a in AminoAcid::SYMBOLS {
+                for &b in AminoAcid::SYMBOLS {
+                    assert_eq!(matrix.score(a, b), matrix.score(b, a))
+                }
+            }
+        }
+        let blosum62 = SubstitutionMatrix::blosum62();
+        assert_eq!(blosum62.score(AminoAcid::W, AminoAcid::W), 11);
+        assert_eq!(blosum62.score(AminoAcid::A, AminoAcid::R), -1);
+        assert_eq!(blosum62.score(AminoAcid::Stop, AminoAcid::A), -4);
+        assert_eq!(
+            SubstitutionMatrix::pam250().score(AminoAcid::C, AminoAcid::C),
+            12
+        )
+    }
+
+    #[test]
+    fn parse_rejects_incomplete_matrix() {
+        let matrix = "   A  C\nA  1 -1\nC -1  1\n";
+        assert_eq!(
+            SubstitutionMatrix::try_from(matrix),
+            Err("Missing score for A and D".to_string())
+        );
+        assert!(SubstitutionMatrix::try_from("   A  C\nA  1\n").is_err())
+    }
+}
@@ -0,0 +1,281 @@
+use super::scoring::Scoring;
+use super::Aligner;
+use crate::utils::sequence::Alphabet;
+
+/// Lanes of 16 bit scores in a 128 bit vector
+const LANES: usize = 8;
+
+/// Score-only Smith-Waterman alignment of a fixed query against many targets.
+/// The query profile is laid out for Farrar's striped algorithm, which scores
+/// eight query positions at once using SSE2 on x86_64. Other targets, and
+/// scores too large for 16 bits, use a scalar implementation.
+pub struct LocalScorer<T> {
+    query: Vec<T>,
+    /// The score of every query position against every symbol, indexed by
+    /// `symbol.index() * query.len() + position`
+    scores: Vec<i32>,
+    /// For each symbol, the scores of each striped segment of the query,
+    /// with position `segment + lane * segments` in each lane
+    profile: Option<Vec<i16>>,
+    segments: usize,
+    max_score: i32,
+    gap_open: i32,
+    gap_extend: i32,
+}
+
+impl<F> Aligner<F> {
+    /// Prepare to score local alignments of `query` against other sequences
+    pub fn local_scorer<T>(&self, query: &[T]) -> LocalScorer<T>
+    where
+        T: Alphabet,
+        F: Scoring<T>,
+    {
+        LocalScorer::new(query, &self.scoring, self.gap_open, self.gap_extend)
+    }
+}
+
+impl<T: Alphabet> LocalScorer<T> {
+    fn new<F: Scoring<T>>(query: &[T], scoring: &F, gap_open: i32, gap_extend: i32) -> Self {
+        let scores: Vec<i32> = T::SYMBOLS
+            .iter()
+            .flat_map(|&symbol| query.iter().map(move |&q| scoring.score(q, symbol)))
+            .collect();
+        let max_score = scores.iter().copied().max().unwrap_or(0);
+
+        let segments = (query.len() + LANES - 1) / LANES;
+        let fits = |x: i32| x >= i16::MIN as i32 && x <= i16::MAX as i32;
+        // The lazy F loop relies on extending a gap never costing more than opening one
+        let profile = if scores.iter().all(|&s| fits(s))
+            && fits(gap_open)
+            && fits(gap_extend)
+            && gap_extend <= gap_open
+        {
+            let mut profile = vec![0; T::SYMBOLS.len() * segments * LANES];
+            for symbol in 0..T::SYMBOLS.len() {
+                for segment in 0..segments {
+                    for lane in 0..LANES {
+                        let position = segment + lane * segments;
+                        if position < query.len() {
+                            profile[(symbol * segments + segment) * LANES + lane] =
+                                scores[symbol * query.len() + position] as i16;
+                        }
+                    }
+                }
+            }
+            Some(profile)
+        } else {
+            None
+        };
+
+        LocalScorer {
+            query: query.to_vec(),
+            scores,
+            profile,
+            segments,
+            max_score,
+            gap_open,
+            gap_extend,
+        }
+    }
+
+    /// The best local alignment score of the query against `target`
+    pub fn score(&self, target: &[T]) -> i32 {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if let Some(profile) = &self.profile {
+                // SSE2 is always available on x86_64
+                let score = unsafe { sse2::striped_score(profile, self.segments, target, self) };
+                // Scores this close to the limit may have saturated
+                if score < i16::MAX as i32 - self.max_score.max(0) {
+                    return score;
+                }
+            }
+        }
+        self.score_scalar(target)
+    }
+
+    /// The best local alignment score of the query against `target`, one cell at a time
+    pub fn score_scalar(&self, target: &[T]) -> i32 {
+        let (open, extend) = (self.gap_open, self.gap_extend);
+        let m = self.query.len();
+        // Scores of the previous target column, and of alignments ending in
+        // a gap in the query, for each query position
+        let mut scores = vec![0; m + 1];
+        let mut insertions = vec![i32::MIN / 4; m + 1];
+        let mut best = 0;
+        for &symbol in target {
+            let column = &self.scores[symbol.index() * m..(symbol.index() + 1) * m];
+            let mut diagonal = 0;
+            let mut deletion = i32::MIN / 4;
+            let mut above = 0;
+            for i in 1..=m {
+                insertions[i] = (insertions[i] - extend).max(scores[i] - open);
+                deletion = (deletion - extend).max(above - open);
+                let score = (diagonal + column[i - 1])
+                    .max(insertions[i])
+                    .max(deletion)
+                    .max(0);
+                diagonal = scores[i];
+                scores[i] = score;
+                above = score;
+                best = best.max(score);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod sse2 {
+    use super::{LocalScorer, LANES};
+    use crate::utils::sequence::Alphabet;
+    use std::arch::x86_64::*;
+
+    /// Farrar's striped Smith-Waterman over saturating 16 bit lanes
+    ///
+    /// # Safety
+    ///
+    /// Requires SSE2, which every x86_64 processor supports.
+    pub(super) unsafe fn striped_score<T: Alphabet>(
+        profile: &[i16],
+        segments: usize,
+        target: &[T],
+        scorer: &LocalScorer<T>,
+    ) -> i32 {
+        if segments == 0 {
+            return 0;
+        }
+        let open = _mm_set1_epi16(scorer.gap_open as i16);
+        let extend = _mm_set1_epi16(scorer.gap_extend as i16);
+        let zero = _mm_setzero_si128();
+
+        let mut load = vec![zero; segments];
+        let mut store = vec![zero; segments];
+        let mut insertions = vec![zero; segments];
+        let mut best = zero;
+
+        for &symbol in target {
+            let symbol_profile = &profile[symbol.index() * segments * LANES..];
+            let mut deletion = zero;
+            // The diagonal predecessor of each lane's first position is the
+            // last position of the previous lane
+            let mut score = _mm_slli_si128(store[segments - 1], 2);
+            std::mem::swap(&mut load, &mut store);
+
+            for i in 0..segments {
+                let scores =
+                    _mm_loadu_si128(symbol_profile[i * LANES..].as_ptr() as *const __m128i);
+                score = _mm_adds_epi16(score, scores);
+                score = _mm_max_epi16(score, insertions[i]);
+                score = _mm_max_epi16(score, deletion);
+                score = _mm_max_epi16(score, zero);
+                best = _mm_max_epi16(best, score);
+                store[i] = score;
+
+                let opened = _mm_subs_epi16(score, open);
+                insertions[i] = _mm_max_epi16(_mm_subs_epi16(insertions[i], extend), opened);
+                deletion = _mm_max_epi16(_mm_subs_epi16(deletion, extend), opened);
+                score = load[i];
+            }
+
+            // Deletions carried over from the last position of each lane may
+            // still improve scores in the next lane. Keep going round the
+            // segments until no lane's deletion beats opening a gap from the
+            // score already there, as then it cannot improve anything further.
+            deletion = _mm_insert_epi16(_mm_slli_si128(deletion, 2), i16::MIN as i32, 0);
+            let mut i = 0;
+            loop {
+                let opened = _mm_subs_epi16(store[i], open);
+                if _mm_movemask_epi8(_mm_cmpgt_epi16(deletion, opened)) == 0 {
+                    break;
+                }
+                let score = _mm_max_epi16(store[i], deletion);
+                store[i] = score;
+                best = _mm_max_epi16(best, score);
+                insertions[i] = _mm_max_epi16(insertions[i], _mm_subs_epi16(score, open));
+                deletion = _mm_subs_epi16(deletion, extend);
+                i += 1;
+                if i == segments {
+                    i = 0;
+                    deletion = _mm_insert_epi16(_mm_slli_si128(deletion, 2), i16::MIN as i32, 0);
+                }
+            }
+        }
+
+        let mut lanes = [0i16; LANES];
+        _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, best);
+        lanes.iter().copied().max().unwrap() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::alignment::scoring::{MatchMismatch, SubstitutionMatrix};
+    use crate::utils::dna::DnaBase;
+    use crate::utils::protein::AminoAcid;
+
+    fn random_symbols<T: Alphabet>(seed: &mut u64, length: usize) -> Vec<T> {
+        (0..length)
+            .map(|_| {
+                *seed = seed
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                T::SYMBOLS[(*seed >> 33) as usize % T::SYMBOLS.len()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn striped_scores_match_scalar_aligner() {
+        let mut seed = 3;
+        let proteins = Aligner::affine(SubstitutionMatrix::blosum62(), 11, 1);
+        let dna = Aligner::affine(MatchMismatch::new(2, -3), 5, 2);
+        for round in 0..100 {
+            let query: Vec<AminoAcid> = random_symbols(&mut seed, round % 50);
+            let mut target: Vec<AminoAcid> = random_symbols(&mut seed, round * 7 % 60);
+            // Plant part of the query so that there is something to find
+            target.extend(query.iter().skip(round % 5).take(20));
+            let scorer = proteins.local_scorer(&query);
+            let expected = proteins.local_symbols(&query, &target).score();
+            assert_eq!(scorer.score_scalar(&target), expected);
+            assert_eq!(scorer.score(&target), expected);
+
+            let query: Vec<DnaBase> = random_symbols(&mut seed, round % 70);
+            let target: Vec<DnaBase> = random_symbols(&mut seed, round * 3 % 70);
+            let scorer = dna.local_scorer(&query);
+            assert_eq!(
+                scorer.score(&target),
+                dna.local_symbols(&query, &target).score()
+            );
+        }
+
+        // Linear gaps, where extending a gap costs the same as opening one
+        for gap in 1..=3 {
+            let proteins = Aligner::new(SubstitutionMatrix::blosum62(), gap);
+            let dna = Aligner::new(MatchMismatch::new(1, -1), gap);
+            for round in 0..300 {
+                let query: Vec<AminoAcid> = random_symbols(&mut seed, round % 40);
+                let target: Vec<AminoAcid> = random_symbols(&mut seed, round * 7 % 60);
+                let scorer = proteins.local_scorer(&query);
+                let expected = proteins.local_symbols(&query, &target).score();
+                assert_eq!(scorer.score_scalar(&target), expected);
+                assert_eq!(scorer.score(&target), expected);
+
+                let query: Vec<DnaBase> = random_symbols(&mut seed, round % 30);
+                let target: Vec<DnaBase> = random_symbols(&mut seed, round * 3 % 90);
+                let scorer = dna.local_scorer(&query);
+                let expected = dna.local_symbols(&query, &target).score();
+                assert_eq!(scorer.score_scalar(&target), expected);
+                assert_eq!(scorer.score(&target), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn saturated_scores_fall_back_to_scalar() {
+        let aligner = Aligner::new(MatchMismatch::new(100, -100), 100);
+        let query = vec![DnaBase::A; 400];
+        assert_eq!(aligner.local_scorer(&query).score(&query), 40_000)
+    }
+}
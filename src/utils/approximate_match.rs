@@ -0,0 +1,331 @@
+use crate::utils::dna::{Dna, DnaBase};
+use std::collections::BTreeMap;
+
+/// The strand of the text a match was found on
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// How the distance between a pattern and a text window is measured
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Metric {
+    /// Substitutions only, so matches are the same length as the pattern
+    Hamming,
+    /// Substitutions, insertions and deletions
+    Edit,
+}
+
+/// An approximate occurrence of a pattern at `text[start..end]`, in forward strand coordinates
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub distance: usize,
+    pub strand: Strand,
+}
+
+/// Find all windows of `text` within `max_mismatches` substitutions of `pattern`,
+/// as `(start, distance)` pairs
+pub fn hamming_search<T: Eq>(
+    text: &[T],
+    pattern: &[T],
+    max_mismatches: usize,
+) -> Vec<(usize, usize)> {
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return Vec::new();
+    }
+    text.windows(pattern.len())
+        .enumerate()
+        .filter_map(|(i, window)| {
+            let mut distance = 0;
+            for (a, b) in window.iter().zip(pattern) {
+                if a != b {
+                    distance += 1;
+                    if distance > max_mismatches {
+                        return None;
+                    }
+                }
+            }
+            Some((i, distance))
+        })
+        .collect()
+}
+
+fn base_index(base: DnaBase) -> usize {
+    match base {
+        DnaBase::A => 0,
+        DnaBase::C => 1,
+        DnaBase::G => 2,
+        DnaBase::T => 3,
+    }
+}
+
+/// Find every end position in `text` where some substring is within
+/// `max_edits` of `pattern`, as `(end, distance)` pairs with `end` exclusive.
+/// Uses Myers' bit-parallel algorithm for patterns of up to 64 bases.
+pub fn edit_search(text: &[DnaBase], pattern: &[DnaBase], max_edits: usize) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    if pattern.len() > 64 {
+        return edit_search_dp(text, pattern, max_edits);
+    }
+
+    let m = pattern.len();
+    let mut peq = [0u64; 4];
+    for (i, &base) in pattern.iter().enumerate() {
+        peq[base_index(base)] |= 1 << i;
+    }
+    let high_bit = 1u64 << (m - 1);
+
+    let mut pv = !0u64;
+    let mut mv = 0u64;
+    let mut score = m;
+    let mut ends = Vec::new();
+
+    for (j, &base) in text.iter().enumerate() {
+        let eq = peq[base_index(base)];
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+        if ph & high_bit != 0 {
+            score += 1;
+        } else if mh & high_bit != 0 {
+            score -= 1;
+        }
+        ph <<= 1;
+        mh <<= 1;
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+
+        if score <= max_edits {
+            ends.push((j + 1, score));
+        }
+    }
+    ends
+}
+
+/// Column by column edit distance search for patterns too long for a single machine word
+fn edit_search_dp(text: &[DnaBase], pattern: &[DnaBase], max_edits: usize) -> Vec<(usize, usize)> {
+    let mut column: Vec<usize> = (0..=pattern.len()).collect();
+    let mut ends = Vec::new();
+    for (j, base) in text.iter().enumerate() {
+        let mut diagonal = column[0];
+        for i in 1..=pattern.len() {
+            let cost = usize::from(pattern[i - 1] != *base);
+            let value = (diagonal + cost).min(column[i] + 1).min(column[i - 1] + 1);
+            diagonal = column[i];
+            column[i] = value;
+        }
+        if column[pattern.len()] <= max_edits {
+            ends.push((j + 1, column[pattern.len()]));
+        }
+    }
+    ends
+}
+
+/// The latest start for an occurrence of `pattern` ending at `end` with the given distance
+fn edit_match_start(text: &[DnaBase], pattern: &[DnaBase], end: usize, distance: usize) -> usize {
+    let window_start = end.saturating_sub(pattern.len() + distance);
+    let window = &text[window_start..end];
+
+    // Align the reversed pattern against the window read backwards from `end`
+    let mut previous: Vec<usize> = (0..=pattern.len()).collect();
+    if previous[pattern.len()] == distance {
+        return end;
+    }
+    for (j, base) in window.iter().rev().enumerate() {
+        let mut current = vec![0; pattern.len() + 1];
+        current[0] = j + 1;
+        for i in 1..=pattern.len() {
+            let cost = usize::from(pattern[pattern.len() - i] != *base);
+            current[i] = (previous[i - 1] + cost)
+                .min(previous[i] + 1)
+                .min(current[i - 1] + 1);
+        }
+        if current[pattern.len()] == distance {
+            return end - (j + 1);
+        }
+        previous = current;
+    }
+    window_start
+}
+
+fn forward_matches(
+    text: &Dna,
+    pattern: &Dna,
+    max_distance: usize,
+    metric: Metric,
+    strand: Strand,
+) -> Vec<Match> {
+    let (text, pattern) = (text.sequence(), pattern.sequence());
+    match metric {
+        Metric::Hamming => hamming_search(text, pattern, max_distance)
+            .into_iter()
+            .map(|(start, distance)| Match {
+                start,
+                end: start + pattern.len(),
+                distance,
+                strand,
+            })
+            .collect(),
+        Metric::Edit => edit_search(text, pattern, max_distance)
+            .into_iter()
+            .map(|(end, distance)| Match {
+                start: edit_match_start(text, pattern, end, distance),
+                end,
+                distance,
+                strand,
+            })
+            .collect(),
+    }
+}
+
+/// Find all approximate occurrences of `pattern` on either strand of `text`
+pub fn approximate_matches(
+    text: &Dna,
+    pattern: &Dna,
+    max_distance: usize,
+    metric: Metric,
+) -> Vec<Match> {
+    let mut matches = forward_matches(text, pattern, max_distance, metric, Strand::Forward);
+
+    let mut reverse_pattern = pattern.clone();
+    reverse_pattern.reverse_complement();
+    matches.extend(forward_matches(
+        text,
+        &reverse_pattern,
+        max_distance,
+        metric,
+        Strand::Reverse,
+    ));
+
+    matches.sort();
+    matches
+}
+
+/// All sequences within `d` substitutions of `pattern`, including itself
+pub(crate) fn neighbours(pattern: &[DnaBase], d: usize) -> Vec<Vec<DnaBase>> {
+    if d == 0 || pattern.is_empty() {
+        return vec![pattern.to_vec()];
+    }
+    let mut result = Vec::new();
+    for suffix in neighbours(&pattern[1..], d) {
+        let distance = pattern[1..]
+            .iter()
+            .zip(&suffix)
+            .filter(|(a, b)| a != b)
+            .count();
+        if distance < d {
+            for &base in &[DnaBase::A, DnaBase::C, DnaBase::G, DnaBase::T] {
+                let mut neighbour = vec![base];
+                neighbour.extend(&suffix);
+                result.push(neighbour);
+            }
+        } else {
+            let mut neighbour = vec![pattern[0]];
+            neighbour.extend(suffix);
+            result.push(neighbour);
+        }
+    }
+    result
+}
+
+/// Find the most frequent `k`-mers in `text` allowing up to `d` mismatches,
+/// optionally also counting occurrences of each k-mer's reverse complement
+pub fn frequent_words_with_mismatches(
+    text: &Dna,
+    k: usize,
+    d: usize,
+    reverse_complements: bool,
+) -> Vec<Dna> {
+    let mut counts: BTreeMap<Vec<DnaBase>, usize> = BTreeMap::new();
+    if k == 0 {
+        return Vec::new();
+    }
+    for window in text.sequence().windows(k) {
+        for neighbour in neighbours(window, d) {
+            if reverse_complements {
+                let reverse: Vec<DnaBase> =
+                    neighbour.iter().rev().map(|b| b.complement()).collect();
+                *counts.entry(reverse).or_insert(0) += 1;
+            }
+            *counts.entry(neighbour).or_insert(0) += 1;
+        }
+    }
+
+    let max = counts.values().copied().max().unwrap_or(0);
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count == max)
+        .map(|(kmer, _)| Dna::from(kmer))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn hamming_matches_with_three_mismatches() {
+        let text = Dna::try_from("CGCCCGAATCCAGAACGCATTCCCATATTTCGGGACCACTGGCCTCCACGGTACGGACGTCAATCAAATGCCTAGCGGCTTGTGGTTTCTCCTACGCTCC").unwrap();
+        let pattern = Dna::try_from("ATTCTGGA").unwrap();
+        let starts: Vec<usize> = hamming_search(text.sequence(), pattern.sequence(), 3)
+            .into_iter()
+            .map(|(start, _)| start)
+            .collect();
+        assert_eq!(starts, vec![6, 7, 26, 27, 78])
+    }
+
+    #[test]
+    fn myers_agrees_with_dynamic_programming() {
+        let text = Dna::try_from("ACGTTGCATGTCGCATGATGCATGAGAGCTTTACGGATCGA").unwrap();
+        for pattern in &["ATGCA", "GCATGAGAG", "TTTTT", "ACG"] {
+            let pattern = Dna::try_from(*pattern).unwrap();
+            for k in 0..3 {
+                assert_eq!(
+                    edit_search(text.sequence(), pattern.sequence(), k),
+                    edit_search_dp(text.sequence(), pattern.sequence(), k)
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn matches_are_found_on_both_strands() {
+        let text = Dna::try_from("TTTGCATGTTTACATGCTT").unwrap();
+        let pattern = Dna::try_from("GCATG").unwrap();
+        let matches = approximate_matches(&text, &pattern, 0, Metric::Edit);
+        assert_eq!(
+            matches,
+            vec![
+                Match {
+                    start: 3,
+                    end: 8,
+                    distance: 0,
+                    strand: Strand::Forward
+                },
+                Match {
+                    start: 12,
+                    end: 17,
+                    distance: 0,
+                    strand: Strand::Reverse
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn frequent_words_with_mismatches_and_reverse_complements() {
+        let text = Dna::try_from("ACGTTGCATGTCGCATGATGCATGAGAGCT").unwrap();
+        let words: Vec<String> = frequent_words_with_mismatches(&text, 4, 1, true)
+            .iter()
+            .map(|dna| dna.to_string())
+            .collect();
+        assert_eq!(words, vec!["ACAT", "ATGT"])
+    }
+}
@@ -1,66 +1,281 @@
-use crate::utils::dna::Dna;
-use crate::utils::fasta::Fasta;
-use std::collections::BTreeMap;
-use std::collections::BTreeSet;
-
-#[derive(Eq, PartialEq, PartialOrd, Ord, Debug)]
+/// A named node payload, for graphs whose nodes carry a value as well as an id
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
 pub struct Node<T: Eq> {
     name: String,
     value: T,
 }
 
 impl<T: Eq> Node<T> {
-    fn new(name: String, value: T) -> Self {
+    pub fn new(name: String, value: T) -> Self {
         Self { name, value }
     }
 
     pub fn name(&self) -> String {
         self.name.to_string()
     }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
 }
 
-pub struct Digraph<T: Eq> {
-    edges: BTreeMap<Node<T>, BTreeSet<Node<T>>>,
+/// An edge between two node indices. Undirected edges are stored once, with
+/// `from` and `to` in the order they were added.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
 }
 
-impl<T: Eq + Ord> Digraph<T> {
-    fn new() -> Self {
-        Self {
-            edges: BTreeMap::new(),
+/// A directed or undirected graph with a payload of type `N` on each node and
+/// a weight of type `W` on each edge. Nodes and edges are numbered from 0 in
+/// the order they are added.
+#[derive(Debug, Clone)]
+pub struct Graph<N, W> {
+    directed: bool,
+    nodes: Vec<N>,
+    edges: Vec<Edge<W>>,
+    /// The indices of the edges leaving each node, in the order they were added
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<N, W> Graph<N, W> {
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            adjacency: Vec::new(),
         }
     }
 
-    fn insert_edge(&mut self, from: Node<T>, to: Node<T>) {
-        self.edges
-            .entry(from)
-            .or_insert_with(BTreeSet::new)
-            .insert(to);
+    pub fn is_directed(&self) -> bool {
+        self.directed
     }
 
-    pub fn adjacency_list(&self) -> Vec<(&Node<T>, &Node<T>)> {
-        let mut adj_list = Vec::new();
-        for (node1, nodelist) in &self.edges {
-            for node2 in nodelist {
-                adj_list.push((node1, node2))
-            }
+    /// Add a node, returning its index
+    pub fn add_node(&mut self, payload: N) -> usize {
+        self.nodes.push(payload);
+        self.adjacency.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    /// Add an edge between existing nodes, returning its index
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) -> usize {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "Edge {} -> {} refers to a missing node",
+            from,
+            to
+        );
+        let edge = self.edges.len();
+        self.edges.push(Edge { from, to, weight });
+        self.adjacency[from].push(edge);
+        if !self.directed && from != to {
+            self.adjacency[to].push(edge);
         }
-        adj_list
+        edge
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn node(&self, node: usize) -> &N {
+        &self.nodes[node]
+    }
+
+    pub fn node_mut(&mut self, node: usize) -> &mut N {
+        &mut self.nodes[node]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// The first node whose payload matches
+    pub fn find_node(&self, predicate: impl Fn(&N) -> bool) -> Option<usize> {
+        self.nodes.iter().position(predicate)
+    }
+
+    /// The nodes reachable from `node` along one edge, with the edge weights.
+    /// Undirected edges can be followed from either end.
+    pub fn neighbours(&self, node: usize) -> impl Iterator<Item = (usize, &W)> + '_ {
+        self.adjacency[node].iter().map(move |&edge| {
+            let edge = &self.edges[edge];
+            let other = if edge.from == node {
+                edge.to
+            } else {
+                edge.from
+            };
+            (other, &edge.weight)
+        })
+    }
+
+    /// The number of edges at each node: for directed graphs, those leaving it
+    pub fn degree(&self, node: usize) -> usize {
+        self.adjacency[node].len()
     }
 }
 
-impl Digraph<&Dna> {
-    pub fn overlap_graph_from_fasta_dna(fasta: &Fasta<Dna>, k: usize) -> Digraph<&Dna> {
-        let mut graph = Digraph::new();
-        for node1 in fasta.iter() {
-            for node2 in fasta.iter() {
-                if node1 != node2 && node1.1.suffix(k) == node2.1.prefix(k) {
-                    graph.insert_edge(
-                        Node::new(node1.0.to_string(), &node1.1),
-                        Node::new(node2.0.to_string(), &node2.1),
-                    )
-                }
-            }
+impl<N: Clone, W: Clone> Graph<N, W> {
+    /// The same graph with every edge reversed
+    pub fn reversed(&self) -> Self {
+        let mut graph = Graph::new(self.directed);
+        for payload in &self.nodes {
+            graph.add_node(payload.clone());
+        }
+        for edge in &self.edges {
+            graph.add_edge(edge.to, edge.from, edge.weight.clone());
         }
         graph
     }
 }
+
+impl Graph<(), i64> {
+    /// Parse Rosalind's edge list format: a line `n m` giving the number of
+    /// nodes and edges, then `m` lines `u v` or `u v w` with nodes numbered
+    /// from 1. Nodes are numbered from 0 in the graph, and unweighted edges
+    /// have weight 1.
+    pub fn from_edge_list(s: &str, directed: bool) -> Result<Self, String> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        let numbers = |line: &str| {
+            line.split_whitespace()
+                .map(|x| x.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| format!("Invalid edge list line: {}", line))
+        };
+        let header = lines
+            .next()
+            .ok_or_else(|| "Missing edge list header".to_string())?;
+        let (nodes, edges) = match numbers(header)?[..] {
+            [nodes, edges] if nodes >= 0 && edges >= 0 => (nodes as usize, edges as usize),
+            _ => return Err(format!("Invalid edge list header: {}", header)),
+        };
+
+        let mut graph = Graph::new(directed);
+        for _ in 0..nodes {
+            graph.add_node(());
+        }
+        let node = |x: i64| {
+            if x >= 1 && x as usize <= nodes {
+                Ok(x as usize - 1)
+            } else {
+                Err(format!("Node {} is not between 1 and {}", x, nodes))
+            }
+        };
+        for line in lines.by_ref().take(edges) {
+            let (from, to, weight) = match numbers(line)?[..] {
+                [u, v] => (u, v, 1),
+                [u, v, w] => (u, v, w),
+                _ => return Err(format!("Invalid edge: {}", line)),
+            };
+            graph.add_edge(node(from)?, node(to)?, weight);
+        }
+        if graph.edge_count() != edges {
+            return Err(format!(
+                "Expected {} edges, found {}",
+                edges,
+                graph.edge_count()
+            ));
+        }
+        Ok(graph)
+    }
+
+    /// Parse several graphs in edge list format: a line giving the number of
+    /// graphs, then the edge list of each graph after a blank line
+    pub fn from_edge_lists(s: &str, directed: bool) -> Result<Vec<Self>, String> {
+        let mut lines = s.lines().map(str::trim).skip_while(|line| line.is_empty());
+        let header = lines
+            .next()
+            .ok_or_else(|| "Missing number of graphs".to_string())?;
+        let count: usize = header
+            .parse()
+            .map_err(|_| format!("Invalid number of graphs: {}", header))?;
+        let graphs = lines
+            .collect::<Vec<_>>()
+            .split(|line| line.is_empty())
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| Self::from_edge_list(&lines.join("\n"), directed))
+            .collect::<Result<Vec<_>, _>>()?;
+        if graphs.len() != count {
+            return Err(format!("Expected {} graphs, found {}", count, graphs.len()));
+        }
+        Ok(graphs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_weighted_edge_lists() {
+        let graph = Graph::from_edge_list("3 3\n1 2 4\n2 3 -1\n3 1 2", true).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(
+            graph.edges()[1],
+            Edge {
+                from: 1,
+                to: 2,
+                weight: -1
+            }
+        );
+        assert_eq!(graph.neighbours(0).collect::<Vec<_>>(), vec![(1, &4)]);
+        assert_eq!(
+            graph.reversed().neighbours(0).collect::<Vec<_>>(),
+            vec![(2, &2)]
+        );
+
+        let undirected = Graph::from_edge_list("3 2\n1 2\n2 3\n", false).unwrap();
+        assert_eq!(
+            undirected.neighbours(1).collect::<Vec<_>>(),
+            vec![(0, &1), (2, &1)]
+        );
+        assert_eq!(undirected.degree(2), 1);
+
+        assert!(Graph::from_edge_list("2 1\n1 3", true).is_err());
+        assert!(Graph::from_edge_list("2 2\n1 2", true).is_err());
+
+        let graphs = Graph::from_edge_lists("2\n\n2 1\n1 2\n\n3 0\n", false).unwrap();
+        assert_eq!(
+            graphs.iter().map(Graph::node_count).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(Graph::from_edge_lists("3\n\n2 1\n1 2", false).is_err())
+    }
+
+    #[test]
+    fn node_payloads_and_dot() {
+        let mut graph = Graph::undirected();
+        let a = graph.add_node(Node::new("a".to_string(), "x\"y".to_string()));
+        let b = graph.add_node(Node::new("b".to_string(), String::new()));
+        graph.add_edge(a, b, ());
+        assert_eq!(graph.find_node(|node| node.name() == "b"), Some(b));
+        assert_eq!(
+            graph.to_dot(),
+            "graph {
+    \"a\" [label=\"x\\\"y\"];
+    \"b\" [label=\"\"];
+    \"a\" -- \"b\";
+}"
+        )
+    }
+}
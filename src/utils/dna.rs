@@ -1,7 +1,7 @@
-use crate::utils::sequence::Sequence;
+use crate::utils::sequence::{Alphabet, Sequence};
 use std::convert::TryFrom;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub enum DnaBase {
     A,
     C,
@@ -9,6 +9,40 @@ pub enum DnaBase {
     T,
 }
 
+impl DnaBase {
+    /// The base paired with this one on the opposite strand
+    pub fn complement(self) -> DnaBase {
+        match self {
+            DnaBase::A => DnaBase::T,
+            DnaBase::C => DnaBase::G,
+            DnaBase::G => DnaBase::C,
+            DnaBase::T => DnaBase::A,
+        }
+    }
+}
+
+impl Alphabet for DnaBase {
+    const SYMBOLS: &'static [DnaBase] = &[DnaBase::A, DnaBase::C, DnaBase::G, DnaBase::T];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl TryFrom<char> for DnaBase {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_lowercase() {
+            'a' => Ok(DnaBase::A),
+            'c' => Ok(DnaBase::C),
+            'g' => Ok(DnaBase::G),
+            't' => Ok(DnaBase::T),
+            c => Err(format!("Failed to convert {} to dna base", c)),
+        }
+    }
+}
+
 impl std::fmt::Display for DnaBase {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -34,6 +68,10 @@ impl Dna {
         self.sequence.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
     pub fn get_intron_locations(&self, introns: &[&Dna]) -> Vec<(usize, usize)> {
         let mut locations = Vec::new();
         for intron in introns {
@@ -71,19 +109,10 @@ impl Dna {
 
     /// Convert this DNA sequence to it's reverse complement
     pub fn reverse_complement(&mut self) {
-        let mut rev_comp = Vec::with_capacity(self.len());
-        for base in self.iter().rev() {
-            rev_comp.push(match base {
-                DnaBase::A => DnaBase::T,
-                DnaBase::C => DnaBase::G,
-                DnaBase::G => DnaBase::C,
-                DnaBase::T => DnaBase::A,
-            })
-        }
-        self.sequence = rev_comp
+        self.sequence = self.iter().rev().map(|base| base.complement()).collect()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<DnaBase> {
+    pub fn iter(&self) -> std::slice::Iter<'_, DnaBase> {
         self.sequence.iter()
     }
 
@@ -138,12 +167,18 @@ impl Dna {
         &self.sequence[(len - k)..]
     }
 
-    pub fn chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<DnaBase> {
+    pub fn chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<'_, DnaBase> {
         self.sequence.chunks_exact(chunk_size)
     }
 }
 
-impl Sequence for Dna {}
+impl Sequence for Dna {
+    type Symbol = DnaBase;
+
+    fn symbols(&self) -> &[DnaBase] {
+        &self.sequence
+    }
+}
 
 impl std::fmt::Display for Dna {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -176,16 +211,10 @@ impl TryFrom<&str> for Dna {
     type Error = String;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut sequence = Vec::with_capacity(value.len());
-        for ch in value.chars() {
-            match ch.to_ascii_lowercase() {
-                'a' => sequence.push(DnaBase::A),
-                'c' => sequence.push(DnaBase::C),
-                'g' => sequence.push(DnaBase::G),
-                't' => sequence.push(DnaBase::T),
-                c => return Err(format!("Failed to convert {} to dna base", c)),
-            }
-        }
+        let sequence = value
+            .chars()
+            .map(DnaBase::try_from)
+            .collect::<Result<_, _>>()?;
         Ok(Dna { sequence })
     }
 }
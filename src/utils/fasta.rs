@@ -1,4 +1,3 @@
-use crate::utils::sequence::Sequence;
 use std::convert::TryFrom;
 use std::fmt::Display;
 use std::fs::File;
@@ -11,7 +10,7 @@ pub struct Fasta<T> {
 
 impl<T> Fasta<T>
 where
-    T: Sequence,
+    T: TryFrom<String>,
     T::Error: ToString,
 {
     pub fn load(path: &str) -> Result<Self, std::io::Error> {
@@ -22,19 +21,34 @@ where
 }
 
 impl<T> Fasta<T> {
-    pub fn iter(&self) -> std::slice::Iter<(String, T)> {
+    pub fn iter(&self) -> std::slice::Iter<'_, (String, T)> {
         self.data.iter()
     }
 
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<(String, T)> {
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, (String, T)> {
         self.data.iter_mut()
     }
 }
 
+impl<T> IntoIterator for Fasta<T> {
+    type Item = (String, T);
+    type IntoIter = std::vec::IntoIter<(String, T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T> From<Vec<(String, T)>> for Fasta<T> {
+    fn from(data: Vec<(String, T)>) -> Self {
+        Fasta { data }
+    }
+}
+
 impl<T: Display> std::fmt::Display for Fasta<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         for (id, item) in &self.data {
-            writeln!(f, "{}", id)?;
+            writeln!(f, ">{}", id)?;
             writeln!(f, "{}", item)?;
         }
         Ok(())
@@ -43,7 +57,7 @@ impl<T: Display> std::fmt::Display for Fasta<T> {
 
 impl<T> TryFrom<File> for Fasta<T>
 where
-    T: Sequence,
+    T: TryFrom<String>,
     T::Error: ToString,
 {
     type Error = io::Error;
@@ -83,7 +97,7 @@ where
 
 impl<T> TryFrom<String> for Fasta<T>
 where
-    T: Sequence,
+    T: TryFrom<String>,
     T::Error: ToString,
 {
     type Error = String;
@@ -95,7 +109,7 @@ where
 
 impl<T> TryFrom<&str> for Fasta<T>
 where
-    T: Sequence,
+    T: TryFrom<String>,
     T::Error: ToString,
 {
     type Error = String;
@@ -0,0 +1,360 @@
+use crate::utils::dna::{Dna, DnaBase};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::Range;
+
+const MAGIC: &[u8; 4] = b"FMI1";
+const SENTINEL: u8 = 0;
+const ALPHABET_SIZE: usize = 5;
+const OCC_SAMPLE_RATE: usize = 64;
+const DEFAULT_SA_SAMPLE_RATE: usize = 32;
+
+fn encode(base: DnaBase) -> u8 {
+    match base {
+        DnaBase::A => 1,
+        DnaBase::C => 2,
+        DnaBase::G => 3,
+        DnaBase::T => 4,
+    }
+}
+
+fn decode(code: u8) -> DnaBase {
+    match code {
+        1 => DnaBase::A,
+        2 => DnaBase::C,
+        3 => DnaBase::G,
+        4 => DnaBase::T,
+        c => panic!("Invalid dna base code in bwt: {}", c),
+    }
+}
+
+/// Build the suffix array of `text` by prefix doubling.
+/// `text` must end with a unique smallest symbol.
+fn suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = text.iter().map(|&c| c as usize).collect();
+    let mut next_rank = vec![0; n];
+    let mut k = 1;
+    while k < n {
+        let key = |i: usize| (rank[i], if i + k < n { rank[i + k] + 1 } else { 0 });
+        sa.sort_by_key(|&i| key(i));
+        next_rank[sa[0]] = 0;
+        for w in 1..n {
+            next_rank[sa[w]] = next_rank[sa[w - 1]] + usize::from(key(sa[w]) != key(sa[w - 1]));
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        if rank[sa[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    sa
+}
+
+/// An FM-index over a DNA sequence, supporting exact pattern counting and
+/// locating by backward search over the Burrows-Wheeler transform.
+#[derive(Debug, PartialEq)]
+pub struct FmIndex {
+    bwt: Vec<u8>,
+    counts: [usize; ALPHABET_SIZE],
+    occ: Vec<[usize; ALPHABET_SIZE]>,
+    sa_sample_rate: usize,
+    sa_samples: BTreeMap<usize, usize>,
+}
+
+impl FmIndex {
+    pub fn new(dna: &Dna) -> Self {
+        Self::with_sample_rate(dna, DEFAULT_SA_SAMPLE_RATE)
+    }
+
+    /// Build the index, keeping every `sa_sample_rate`th text position of the
+    /// suffix array for locating matches
+    pub fn with_sample_rate(dna: &Dna, sa_sample_rate: usize) -> Self {
+        assert!(
+            sa_sample_rate > 0,
+            "Suffix array sample rate must be positive"
+        );
+        let mut text: Vec<u8> = dna.iter().map(|&b| encode(b)).collect();
+        text.push(SENTINEL);
+
+        let sa = suffix_array(&text);
+        let n = text.len();
+        let bwt = sa.iter().map(|&i| text[(i + n - 1) % n]).collect();
+        let sa_samples = sa
+            .iter()
+            .enumerate()
+            .filter(|(_, &pos)| pos % sa_sample_rate == 0)
+            .map(|(row, &pos)| (row, pos))
+            .collect();
+
+        Self::from_parts(bwt, sa_sample_rate, sa_samples)
+    }
+
+    fn from_parts(bwt: Vec<u8>, sa_sample_rate: usize, sa_samples: BTreeMap<usize, usize>) -> Self {
+        let mut totals = [0; ALPHABET_SIZE];
+        let mut occ = Vec::with_capacity(bwt.len() / OCC_SAMPLE_RATE + 1);
+        for (i, &c) in bwt.iter().enumerate() {
+            if i % OCC_SAMPLE_RATE == 0 {
+                occ.push(totals);
+            }
+            totals[c as usize] += 1;
+        }
+        if bwt.len() % OCC_SAMPLE_RATE == 0 {
+            occ.push(totals);
+        }
+
+        let mut counts = [0; ALPHABET_SIZE];
+        for c in 1..ALPHABET_SIZE {
+            counts[c] = counts[c - 1] + totals[c - 1];
+        }
+
+        FmIndex {
+            bwt,
+            counts,
+            occ,
+            sa_sample_rate,
+            sa_samples,
+        }
+    }
+
+    /// The length of the indexed sequence
+    pub fn len(&self) -> usize {
+        self.bwt.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The Burrows-Wheeler transform of the sequence, using `$` as the terminator
+    pub fn bwt(&self) -> String {
+        self.bwt
+            .iter()
+            .map(|&c| {
+                if c == SENTINEL {
+                    "$".to_string()
+                } else {
+                    format!("{}", decode(c))
+                }
+            })
+            .collect()
+    }
+
+    /// Number of occurrences of `code` in `bwt[..row]`
+    fn occ(&self, code: u8, row: usize) -> usize {
+        let checkpoint = row / OCC_SAMPLE_RATE;
+        let start = checkpoint * OCC_SAMPLE_RATE;
+        self.occ[checkpoint][code as usize]
+            + self.bwt[start..row].iter().filter(|&&c| c == code).count()
+    }
+
+    /// Map a row to the row of the suffix one position earlier in the text
+    fn last_to_first(&self, row: usize) -> usize {
+        let code = self.bwt[row];
+        self.counts[code as usize] + self.occ(code, row)
+    }
+
+    /// Find the range of suffix array rows prefixed by `pattern`. An empty
+    /// pattern gives every row except that of the terminator alone, so that
+    /// it matches once at each position of the sequence.
+    pub fn backward_search(&self, pattern: &[DnaBase]) -> Range<usize> {
+        if pattern.is_empty() {
+            return 1..self.bwt.len();
+        }
+        let mut range = 0..self.bwt.len();
+        for &base in pattern.iter().rev() {
+            let code = encode(base);
+            let start = self.counts[code as usize] + self.occ(code, range.start);
+            let end = self.counts[code as usize] + self.occ(code, range.end);
+            if start >= end {
+                return 0..0;
+            }
+            range = start..end;
+        }
+        range
+    }
+
+    /// Count the occurrences of `pattern` in the indexed sequence
+    pub fn count(&self, pattern: &Dna) -> usize {
+        self.backward_search(pattern.sequence()).len()
+    }
+
+    /// Find the 0-based starting positions of `pattern` in the indexed sequence, in increasing order
+    pub fn locate(&self, pattern: &Dna) -> Vec<usize> {
+        let mut positions: Vec<usize> = self
+            .backward_search(pattern.sequence())
+            .map(|row| self.suffix_position(row))
+            .collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Recover the text position of a suffix array row from the sampled entries
+    fn suffix_position(&self, mut row: usize) -> usize {
+        let mut steps = 0;
+        loop {
+            if let Some(pos) = self.sa_samples.get(&row) {
+                return (pos + steps) % self.bwt.len();
+            }
+            row = self.last_to_first(row);
+            steps += 1;
+        }
+    }
+
+    /// Reconstruct the original sequence from the transform
+    pub fn inverse_bwt(&self) -> Dna {
+        let mut sequence = Vec::with_capacity(self.len());
+        let mut row = 0;
+        for _ in 0..self.len() {
+            let code = self.bwt[row];
+            sequence.push(decode(code));
+            row = self.last_to_first(row);
+        }
+        sequence.reverse();
+        Dna::from(sequence)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), io::Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: &str) -> Result<Self, io::Error> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::read_from(&mut reader)
+    }
+
+    /// Serialize the transform and suffix array samples; the rank tables are rebuilt on load
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), io::Error> {
+        writer.write_all(MAGIC)?;
+        for value in &[self.bwt.len(), self.sa_sample_rate, self.sa_samples.len()] {
+            writer.write_all(&(*value as u64).to_le_bytes())?;
+        }
+        writer.write_all(&self.bwt)?;
+        for (row, pos) in &self.sa_samples {
+            writer.write_all(&(*row as u64).to_le_bytes())?;
+            writer.write_all(&(*pos as u64).to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, io::Error> {
+        fn read_usize<R: Read>(reader: &mut R) -> Result<usize, io::Error> {
+            let mut buf = [0; 8];
+            reader.read_exact(&mut buf)?;
+            Ok(u64::from_le_bytes(buf) as usize)
+        }
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("Not an fm index file"));
+        }
+        let len = read_usize(reader)?;
+        let sa_sample_rate = read_usize(reader)?;
+        let sample_count = read_usize(reader)?;
+
+        // Read without trusting the length enough to allocate it up front
+        let mut bwt = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut bwt)?;
+        if bwt.len() != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Truncated bwt in fm index file",
+            ));
+        }
+        if bwt.iter().any(|&c| c as usize >= ALPHABET_SIZE)
+            || bwt.iter().filter(|&&c| c == SENTINEL).count() != 1
+        {
+            return Err(invalid("Corrupt bwt in fm index file"));
+        }
+
+        let mut sa_samples = BTreeMap::new();
+        for _ in 0..sample_count {
+            let row = read_usize(reader)?;
+            let pos = read_usize(reader)?;
+            if row >= len || pos >= len {
+                return Err(invalid("Corrupt suffix array sample in fm index file"));
+            }
+            sa_samples.insert(row, pos);
+        }
+        if sa_sample_rate == 0 || !sa_samples.values().any(|&pos| pos == 0) {
+            return Err(invalid("Missing suffix array samples in fm index file"));
+        }
+
+        Ok(Self::from_parts(bwt, sa_sample_rate, sa_samples))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn naive_locations(text: &Dna, pattern: &Dna) -> Vec<usize> {
+        if pattern.is_empty() {
+            return (0..text.len()).collect();
+        }
+        text.sequence()
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, w)| *w == pattern.sequence())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn bwt_uses_dollar_terminator() {
+        let index = FmIndex::new(&Dna::try_from("GCGTGCCTGGTCA").unwrap());
+        assert_eq!(index.bwt(), "ACTGGCT$TGCGGC")
+    }
+
+    #[test]
+    fn count_and_locate_match_naive_search() {
+        let text = Dna::try_from("GATATATGCATATACTTATAT").unwrap();
+        for sample_rate in &[1, 3, 32] {
+            let index = FmIndex::with_sample_rate(&text, *sample_rate);
+            for pattern in &["ATAT", "A", "GCA", "TTT", "GATATATGCATATACTTATAT", ""] {
+                let pattern = Dna::try_from(*pattern).unwrap();
+                let expected = naive_locations(&text, &pattern);
+                assert_eq!(index.count(&pattern), expected.len());
+                assert_eq!(index.locate(&pattern), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_bwt_recovers_sequence() {
+        let text = Dna::try_from("ACGTTGCAAACCGGTTTACG").unwrap();
+        assert_eq!(FmIndex::new(&text).inverse_bwt(), text)
+    }
+
+    #[test]
+    fn serialization_round_trips() {
+        let text = Dna::try_from("GATATATGCATATACTT").unwrap();
+        let index = FmIndex::with_sample_rate(&text, 4);
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let loaded = FmIndex::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(
+            loaded.locate(&Dna::try_from("ATAT").unwrap()),
+            vec![1, 3, 9]
+        );
+
+        // A header claiming a huge transform is rejected without allocating it
+        let mut truncated = bytes[..4].to_vec();
+        truncated.extend_from_slice(&u64::MAX.to_le_bytes());
+        truncated.extend_from_slice(&bytes[12..40]);
+        assert_eq!(
+            FmIndex::read_from(&mut truncated.as_slice())
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        )
+    }
+}
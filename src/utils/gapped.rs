@@ -0,0 +1,87 @@
+use std::convert::TryFrom;
+use std::fmt::Display;
+
+/// A row of a multiple alignment: a sequence with `None` marking each gap
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GappedSequence<T> {
+    symbols: Vec<Option<T>>,
+}
+
+impl<T: Copy> GappedSequence<T> {
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    pub fn symbols(&self) -> &[Option<T>] {
+        &self.symbols
+    }
+
+    /// The sequence with its gaps removed
+    pub fn ungapped(&self) -> Vec<T> {
+        self.symbols.iter().filter_map(|&symbol| symbol).collect()
+    }
+}
+
+impl<T> From<Vec<Option<T>>> for GappedSequence<T> {
+    fn from(symbols: Vec<Option<T>>) -> Self {
+        GappedSequence { symbols }
+    }
+}
+
+impl<T: Copy> From<&[T]> for GappedSequence<T> {
+    fn from(symbols: &[T]) -> Self {
+        GappedSequence {
+            symbols: symbols.iter().map(|&symbol| Some(symbol)).collect(),
+        }
+    }
+}
+
+impl<T> TryFrom<&str> for GappedSequence<T>
+where
+    T: TryFrom<char>,
+    T::Error: ToString,
+{
+    type Error = String;
+
+    /// Parse a sequence using `-` or `.` for gaps
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let symbols = s
+            .chars()
+            .map(|c| match c {
+                '-' | '.' => Ok(None),
+                c => T::try_from(c.to_ascii_uppercase())
+                    .map(Some)
+                    .map_err(|e| e.to_string()),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(GappedSequence { symbols })
+    }
+}
+
+impl<T> TryFrom<String> for GappedSequence<T>
+where
+    T: TryFrom<char>,
+    T::Error: ToString,
+{
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str())
+    }
+}
+
+impl<T: Display> Display for GappedSequence<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for symbol in &self.symbols {
+            match symbol {
+                Some(symbol) => write!(f, "{}", symbol)?,
+                None => write!(f, "-")?,
+            }
+        }
+        Ok(())
+    }
+}
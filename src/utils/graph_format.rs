@@ -0,0 +1,327 @@
+use crate::utils::digraph::{Graph, Node};
+use crate::utils::overlap::Record;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// File formats that graphs can be written in
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    GraphMl,
+    /// GFA1, for graphs of overlapping sequences
+    Gfa,
+}
+
+impl GraphFormat {
+    /// A format looked up by name ignoring case
+    pub fn named(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "gfa" => Ok(GraphFormat::Gfa),
+            _ => Err(format!("Unknown graph format: {}", name)),
+        }
+    }
+}
+
+/// How a node payload is identified and labelled when a graph is written out
+pub trait NodeLabel {
+    /// A name for the node, which must differ between nodes. Defaults to the
+    /// node's index counting from 1.
+    fn node_id(&self, index: usize) -> String {
+        (index + 1).to_string()
+    }
+
+    fn node_label(&self) -> Option<String> {
+        None
+    }
+}
+
+/// How an edge weight is labelled when a graph is written out
+pub trait EdgeLabel {
+    fn edge_label(&self) -> Option<String>;
+}
+
+/// A node payload with a sequence, for writing sequence graphs as GFA
+pub trait SequenceNode: NodeLabel {
+    fn segment(&self) -> String;
+}
+
+impl NodeLabel for () {}
+
+impl<T: Eq + Display> NodeLabel for Node<T> {
+    fn node_id(&self, _: usize) -> String {
+        self.name()
+    }
+
+    fn node_label(&self) -> Option<String> {
+        Some(self.value().to_string())
+    }
+}
+
+impl NodeLabel for Record<'_> {
+    fn node_id(&self, _: usize) -> String {
+        self.name().to_string()
+    }
+}
+
+impl SequenceNode for Record<'_> {
+    fn segment(&self) -> String {
+        self.sequence().to_string()
+    }
+}
+
+/// The members of a strongly connected component, as in a condensation
+impl NodeLabel for Vec<usize> {
+    fn node_label(&self) -> Option<String> {
+        Some(
+            self.iter()
+                .map(|node| (node + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+}
+
+impl EdgeLabel for () {
+    fn edge_label(&self) -> Option<String> {
+        None
+    }
+}
+
+impl EdgeLabel for i64 {
+    fn edge_label(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl EdgeLabel for usize {
+    fn edge_label(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<N: NodeLabel, W: EdgeLabel> Graph<N, W> {
+    fn node_ids(&self) -> Vec<String> {
+        self.nodes()
+            .iter()
+            .enumerate()
+            .map(|(index, node)| node.node_id(index))
+            .collect()
+    }
+
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let ids = self.node_ids();
+        let (keyword, arrow) = if self.is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = format!("{} {{\n", keyword);
+        for (id, node) in ids.iter().zip(self.nodes()) {
+            match node.node_label() {
+                Some(label) => dot.push_str(&format!(
+                    "    \"{}\" [label=\"{}\"];\n",
+                    escape_dot(id),
+                    escape_dot(&label)
+                )),
+                None => dot.push_str(&format!("    \"{}\";\n", escape_dot(id))),
+            }
+        }
+        for edge in self.edges() {
+            let label = edge
+                .weight
+                .edge_label()
+                .map(|label| format!(" [label=\"{}\"]", escape_dot(&label)))
+                .unwrap_or_default();
+            dot.push_str(&format!(
+                "    \"{}\" {} \"{}\"{};\n",
+                escape_dot(&ids[edge.from]),
+                arrow,
+                escape_dot(&ids[edge.to]),
+                label
+            ));
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Render the graph as GraphML, with node labels and edge weights as
+    /// string attributes
+    pub fn to_graphml(&self) -> String {
+        let ids = self.node_ids();
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">
+  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>
+  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"string\"/>\n",
+        );
+        xml.push_str(&format!(
+            "  <graph id=\"G\" edgedefault=\"{}\">\n",
+            if self.is_directed() {
+                "directed"
+            } else {
+                "undirected"
+            }
+        ));
+        for (id, node) in ids.iter().zip(self.nodes()) {
+            match node.node_label() {
+                Some(label) => xml.push_str(&format!(
+                    "    <node id=\"{}\"><data key=\"label\">{}</data></node>\n",
+                    escape_xml(id),
+                    escape_xml(&label)
+                )),
+                None => xml.push_str(&format!("    <node id=\"{}\"/>\n", escape_xml(id))),
+            }
+        }
+        for edge in self.edges() {
+            let (from, to) = (escape_xml(&ids[edge.from]), escape_xml(&ids[edge.to]));
+            match edge.weight.edge_label() {
+                Some(weight) => xml.push_str(&format!(
+                    "    <edge source=\"{}\" target=\"{}\"><data key=\"weight\">{}</data></edge>\n",
+                    from,
+                    to,
+                    escape_xml(&weight)
+                )),
+                None => xml.push_str(&format!(
+                    "    <edge source=\"{}\" target=\"{}\"/>\n",
+                    from, to
+                )),
+            }
+        }
+        xml.push_str("  </graph>\n</graphml>");
+        xml
+    }
+}
+
+/// GFA segment names: whitespace is replaced by underscores, an empty name
+/// becomes the node's index counting from 1, and a name already taken gets
+/// the first free suffix of `_2`, `_3` and so on
+fn gfa_names(ids: &[String]) -> Vec<String> {
+    let mut used = HashSet::new();
+    ids.iter()
+        .enumerate()
+        .map(|(index, id)| {
+            let mut name = id.split_whitespace().collect::<Vec<_>>().join("_");
+            if name.is_empty() {
+                name = (index + 1).to_string();
+            }
+            let mut unique = name.clone();
+            let mut suffix = 2;
+            while used.contains(&unique) {
+                unique = format!("{}_{}", name, suffix);
+                suffix += 1;
+            }
+            used.insert(unique.clone());
+            unique
+        })
+        .collect()
+}
+
+impl<N: SequenceNode> Graph<N, usize> {
+    /// Render a graph of sequences joined by overlaps of the length of each
+    /// edge's weight as GFA1, with one segment per node and one link per
+    /// edge. Names are made into valid, distinct segment names as in
+    /// `gfa_names`, and empty sequences are written as `*`.
+    pub fn to_gfa(&self) -> String {
+        let ids = gfa_names(&self.node_ids());
+        let mut gfa = String::from("H\tVN:Z:1.0\n");
+        for (id, node) in ids.iter().zip(self.nodes()) {
+            let segment = node.segment();
+            let segment = if segment.is_empty() { "*" } else { &segment };
+            gfa.push_str(&format!("S\t{}\t{}\n", id, segment));
+        }
+        for edge in self.edges() {
+            gfa.push_str(&format!(
+                "L\t{}\t+\t{}\t+\t{}M\n",
+                ids[edge.from], ids[edge.to], edge.weight
+            ));
+        }
+        gfa.trim_end().to_string()
+    }
+
+    /// Render the graph in any of the supported formats
+    pub fn write(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Gfa => self.to_gfa(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::Dna;
+    use crate::utils::fasta::Fasta;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn weighted_graphs_as_dot_and_graphml() {
+        let graph = Graph::from_edge_list("2 1\n1 2 -3", true).unwrap();
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {
+    \"1\";
+    \"2\";
+    \"1\" -> \"2\" [label=\"-3\"];
+}"
+        );
+        assert!(graph.to_graphml().ends_with(
+            "  <graph id=\"G\" edgedefault=\"directed\">
+    <node id=\"1\"/>
+    <node id=\"2\"/>
+    <edge source=\"1\" target=\"2\"><data key=\"weight\">-3</data></edge>
+  </graph>
+</graphml>"
+        ));
+        assert_eq!(GraphFormat::named("GraphML"), Ok(GraphFormat::GraphMl))
+    }
+
+    #[test]
+    fn overlap_graphs_as_gfa() {
+        let fasta = Fasta::<Dna>::try_from(">a & b\nATTAGAC\n>c\nGACCTA").unwrap();
+        let graph = Graph::overlap_graph(&fasta, 3);
+        assert_eq!(
+            graph.write(GraphFormat::Gfa),
+            "H\tVN:Z:1.0
+S\ta_&_b\tATTAGAC
+S\tc\tGACCTA
+L\ta_&_b\t+\tc\t+\t3M"
+        );
+        assert!(graph.to_graphml().contains("<node id=\"a &amp; b\"/>"))
+    }
+    #[test]
+    fn gfa_names_are_distinct_and_sequences_present() {
+        let fasta =
+            Fasta::<Dna>::try_from(">a b\nACGT\n>a_b\nGTAA\n>a b\nAACC\n>a_b_2\n\n>\nCCA").unwrap();
+        let graph = Graph::overlap_graph(&fasta, 2);
+        assert_eq!(
+            graph.to_gfa(),
+            "H\tVN:Z:1.0
+S\ta_b\tACGT
+S\ta_b_2\tGTAA
+S\ta_b_3\tAACC
+S\ta_b_2_2\t*
+S\t5\tCCA
+L\ta_b\t+\ta_b_2\t+\t2M
+L\ta_b_2\t+\ta_b_3\t+\t2M
+L\ta_b_3\t+\t5\t+\t2M"
+        )
+    }
+}
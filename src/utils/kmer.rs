@@ -0,0 +1,276 @@
+use crate::utils::dna::DnaBase;
+use crate::utils::fasta::Fasta;
+use crate::utils::sequence::{Alphabet, Sequence};
+use std::collections::BTreeMap;
+
+/// The longest k-mers handled by `encode` and the k-mer types built on it:
+/// those for which every possible k-mer has a distinct code below
+/// `base^k` that fits in 64 bits. This is 31 for DNA.
+pub fn max_k<T: Alphabet>() -> usize {
+    let base = T::SYMBOLS.len() as u64;
+    (1..)
+        .take_while(|&k| base.checked_pow(k).is_some())
+        .last()
+        .unwrap_or(0) as usize
+}
+
+/// Encode a k-mer as an integer in base `T::SYMBOLS.len()`, preserving lexicographic order
+pub fn encode<T: Alphabet>(kmer: &[T]) -> u64 {
+    let base = T::SYMBOLS.len() as u64;
+    kmer.iter()
+        .fold(0, |code, symbol| code * base + symbol.index() as u64)
+}
+
+/// Decode an integer produced by `encode` back into a k-mer of length `k`
+pub fn decode<T: Alphabet>(mut code: u64, k: usize) -> Vec<T> {
+    let base = T::SYMBOLS.len() as u64;
+    let mut kmer = vec![T::SYMBOLS[0]; k];
+    for symbol in kmer.iter_mut().rev() {
+        *symbol = T::SYMBOLS[(code % base) as usize];
+        code /= base;
+    }
+    kmer
+}
+
+/// An iterator over the integer encodings of each k-mer of a sequence, computed
+/// by rolling the encoding along the sequence. Given a complement, each k-mer
+/// is encoded canonically as the smaller of itself and its reverse complement.
+pub struct KmerCodes<'a, T> {
+    symbols: std::slice::Iter<'a, T>,
+    complement: Option<fn(T) -> T>,
+    base: u64,
+    modulus: u64,
+    code: u64,
+    reverse_code: u64,
+}
+
+impl<'a, T: Alphabet> KmerCodes<'a, T> {
+    pub fn new(symbols: &'a [T], k: usize, complement: Option<fn(T) -> T>) -> Self {
+        assert!(k > 0, "k must be positive");
+        let base = T::SYMBOLS.len() as u64;
+        let prefix = &symbols[..(k - 1).min(symbols.len())];
+        let rest = if symbols.len() < k {
+            [].iter()
+        } else {
+            symbols[k - 1..].iter()
+        };
+        let reverse_code = match complement {
+            Some(complement) => {
+                let reverse: Vec<T> = prefix.iter().rev().map(|&s| complement(s)).collect();
+                encode(&reverse) * base
+            }
+            None => 0,
+        };
+        Self {
+            symbols: rest,
+            complement,
+            base,
+            modulus: base.pow(k as u32 - 1),
+            code: encode(prefix),
+            reverse_code,
+        }
+    }
+}
+
+impl<T: Alphabet> Iterator for KmerCodes<'_, T> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let &symbol = self.symbols.next()?;
+        self.code = (self.code % self.modulus) * self.base + symbol.index() as u64;
+        Some(match self.complement {
+            Some(complement) => {
+                self.reverse_code = self.reverse_code / self.base
+                    + complement(symbol).index() as u64 * self.modulus;
+                self.code.min(self.reverse_code)
+            }
+            None => self.code,
+        })
+    }
+}
+
+/// Counts of every k-mer seen across a set of sequences, keyed by their integer encoding
+#[derive(Debug)]
+pub struct KmerCounter<T> {
+    k: usize,
+    complement: Option<fn(T) -> T>,
+    counts: BTreeMap<u64, usize>,
+}
+
+impl<T: Alphabet> KmerCounter<T> {
+    /// Panics unless `k` is between 1 and `max_k()`
+    pub fn new(k: usize) -> Self {
+        assert!(
+            k > 0 && k <= max_k::<T>(),
+            "k must be positive and small enough to encode k-mers in 64 bits"
+        );
+        Self {
+            k,
+            complement: None,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_fasta<S: Sequence<Symbol = T>>(fasta: &Fasta<S>, k: usize) -> Self {
+        let mut counter = Self::new(k);
+        for (_, sequence) in fasta.iter() {
+            counter.add(sequence);
+        }
+        counter
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn add<S: Sequence<Symbol = T>>(&mut self, sequence: &S) {
+        self.add_symbols(sequence.symbols())
+    }
+
+    pub fn add_symbols(&mut self, symbols: &[T]) {
+        for code in KmerCodes::new(symbols, self.k, self.complement) {
+            *self.counts.entry(code).or_insert(0) += 1;
+        }
+    }
+
+    fn key(&self, kmer: &[T]) -> u64 {
+        let code = encode(kmer);
+        match self.complement {
+            Some(complement) => {
+                let reverse: Vec<T> = kmer.iter().rev().map(|&s| complement(s)).collect();
+                code.min(encode(&reverse))
+            }
+            None => code,
+        }
+    }
+
+    /// The number of times `kmer` (or its reverse complement, if counting canonically) was seen
+    pub fn count(&self, kmer: &[T]) -> usize {
+        if kmer.len() != self.k {
+            return 0;
+        }
+        self.counts.get(&self.key(kmer)).copied().unwrap_or(0)
+    }
+
+    /// Every counted k-mer with its count, in lexicographic order
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<T>, usize)> + '_ {
+        self.counts
+            .iter()
+            .map(move |(&code, &count)| (decode(code, self.k), count))
+    }
+
+    /// Counts of all possible k-mers in lexicographic order, including those never seen
+    pub fn composition(&self) -> Vec<usize> {
+        let size = (T::SYMBOLS.len() as u64).pow(self.k as u32);
+        (0..size)
+            .map(|code| self.counts.get(&code).copied().unwrap_or(0))
+            .collect()
+    }
+
+    /// The k-mers with the highest count, in lexicographic order
+    pub fn most_frequent(&self) -> Vec<Vec<T>> {
+        let max = self.counts.values().copied().max().unwrap_or(0);
+        self.iter()
+            .filter(|(_, count)| *count == max && max > 0)
+            .map(|(kmer, _)| kmer)
+            .collect()
+    }
+}
+
+impl KmerCounter<DnaBase> {
+    /// A counter that treats each k-mer and its reverse complement as the same,
+    /// keyed by whichever is lexicographically smaller
+    pub fn canonical(k: usize) -> Self {
+        let mut counter = Self::new(k);
+        counter.complement = Some(DnaBase::complement);
+        counter
+    }
+}
+
+/// Find the k-mers that occur at least `t` times within some window of `window_length` symbols
+pub fn find_clumps<T: Alphabet>(
+    symbols: &[T],
+    k: usize,
+    window_length: usize,
+    t: usize,
+) -> Vec<Vec<T>> {
+    if k == 0 || window_length < k || symbols.len() < window_length {
+        return Vec::new();
+    }
+    let codes: Vec<u64> = symbols.windows(k).map(encode).collect();
+    let kmers_per_window = window_length - k + 1;
+
+    let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+    let mut clumps = std::collections::BTreeSet::new();
+    for (i, &code) in codes.iter().enumerate() {
+        let count = counts.entry(code).or_insert(0);
+        *count += 1;
+        if *count >= t {
+            clumps.insert(code);
+        }
+        if i + 1 >= kmers_per_window {
+            *counts.get_mut(&codes[i + 1 - kmers_per_window]).unwrap() -= 1;
+        }
+    }
+    clumps.into_iter().map(|code| decode(code, k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::Dna;
+    use std::convert::TryFrom;
+
+    fn to_string(kmer: &[DnaBase]) -> String {
+        kmer.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        let kmer = Dna::try_from("GATTACA").unwrap();
+        assert_eq!(encode(kmer.sequence()), 0b10_00_11_11_00_01_00);
+        assert_eq!(
+            decode::<DnaBase>(encode(kmer.sequence()), 7),
+            kmer.sequence()
+        )
+    }
+
+    #[test]
+    fn most_frequent_kmers() {
+        let mut counter = KmerCounter::new(4);
+        counter.add(&Dna::try_from("ACGTTGCATGTCGCATGATGCATGAGAGCT").unwrap());
+        let most_frequent: Vec<String> = counter
+            .most_frequent()
+            .iter()
+            .map(|k| to_string(k))
+            .collect();
+        assert_eq!(most_frequent, vec!["CATG", "GCAT"])
+    }
+
+    #[test]
+    fn canonical_kmers_merge_strands() {
+        let mut counter = KmerCounter::canonical(3);
+        counter.add(&Dna::try_from("AAAGTTT").unwrap());
+        let dna = Dna::try_from("AAA").unwrap();
+        assert_eq!(counter.count(dna.sequence()), 2);
+        let dna = Dna::try_from("TTT").unwrap();
+        assert_eq!(counter.count(dna.sequence()), 2);
+        let dna = Dna::try_from("AAG").unwrap();
+        assert_eq!(counter.count(dna.sequence()), 1);
+        let dna = Dna::try_from("ACT").unwrap();
+        assert_eq!(counter.count(dna.sequence()), 1);
+    }
+
+    #[test]
+    fn clumps_in_window() {
+        let text = Dna::try_from(
+            "CGGACTCGACAGATGTGAAGAACGACAATGTGAAGACTCGACACGACAGAGTGAAGAGAAGAGGAAACATTGTAA",
+        )
+        .unwrap();
+        let clumps: Vec<String> = find_clumps(text.sequence(), 5, 50, 4)
+            .iter()
+            .map(|k| to_string(k))
+            .collect();
+        assert_eq!(clumps, vec!["CGACA", "GAAGA"])
+    }
+}
@@ -0,0 +1,209 @@
+use crate::utils::profile::Profile;
+use crate::utils::sequence::Alphabet;
+
+/// Width of each column of the logo in pixels
+const COLUMN_WIDTH: f64 = 30.0;
+/// Height of a column with the largest possible information content
+const STACK_HEIGHT: f64 = 150.0;
+/// Space around the stacks for the axis and labels
+const MARGIN: f64 = 40.0;
+/// Height of capital letters as a fraction of the font size
+const CAP_HEIGHT: f64 = 0.72;
+/// Width of letters as a fraction of the font size
+const LETTER_WIDTH: f64 = 0.66;
+
+/// How to colour the letters of a logo
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ColourScheme {
+    /// Distinct colours for the four nucleotides
+    Nucleotide,
+    /// Amino acids coloured by side chain chemistry: polar, neutral, basic,
+    /// acidic and hydrophobic
+    Chemistry,
+    /// Amino acids coloured from hydrophilic to hydrophobic
+    Hydrophobicity,
+    Monochrome,
+}
+
+impl ColourScheme {
+    /// A scheme looked up by name ignoring case
+    pub fn named(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "nucleotide" => Ok(ColourScheme::Nucleotide),
+            "chemistry" => Ok(ColourScheme::Chemistry),
+            "hydrophobicity" => Ok(ColourScheme::Hydrophobicity),
+            "monochrome" => Ok(ColourScheme::Monochrome),
+            _ => Err(format!("Unknown colour scheme: {}", name)),
+        }
+    }
+
+    /// The fill colour of a letter
+    pub fn colour(self, letter: char) -> &'static str {
+        match self {
+            ColourScheme::Nucleotide => match letter {
+                'A' => "#109648",
+                'C' => "#255c99",
+                'G' => "#f7b32b",
+                'T' | 'U' => "#d62839",
+                _ => "#000000",
+            },
+            ColourScheme::Chemistry => match letter {
+                'G' | 'S' | 'T' | 'Y' | 'C' => "#109648",
+                'N' | 'Q' => "#8e44ad",
+                'K' | 'R' | 'H' => "#255c99",
+                'D' | 'E' => "#d62839",
+                _ => "#000000",
+            },
+            ColourScheme::Hydrophobicity => match letter {
+                'R' | 'K' | 'D' | 'E' | 'N' | 'Q' => "#255c99",
+                'S' | 'G' | 'H' | 'T' | 'A' | 'P' => "#109648",
+                'Y' | 'V' | 'M' | 'C' | 'L' | 'F' | 'I' | 'W' => "#000000",
+                _ => "#888888",
+            },
+            ColourScheme::Monochrome => "#000000",
+        }
+    }
+}
+
+/// The height in bits of each symbol in each column: the column's
+/// information content shared out by symbol frequency, and scaled by the
+/// fraction of rows that are not gaps as WebLogo does, so that a column
+/// of mostly gaps is not drawn as conserved
+pub fn letter_heights<T: Alphabet>(profile: &Profile<T>) -> Vec<Vec<f64>> {
+    profile
+        .frequencies()
+        .iter()
+        .zip(profile.information_content())
+        .zip(profile.occupancy())
+        .map(|((column, information), occupancy)| {
+            column
+                .iter()
+                .map(|&p| p * information * occupancy)
+                .collect()
+        })
+        .collect()
+}
+
+/// Render a sequence logo as an SVG document. Each column is a stack of
+/// letters, tallest on top, scaled so that a fully conserved column fills
+/// the height of the plot.
+pub fn svg<T: Alphabet>(profile: &Profile<T>, scheme: ColourScheme) -> String {
+    let maximum = (T::RESIDUES.len() as f64).log2();
+    let scale = STACK_HEIGHT / maximum;
+    let width = profile.len() as f64 * COLUMN_WIDTH + 2.0 * MARGIN;
+    let height = STACK_HEIGHT + 2.0 * MARGIN;
+    let baseline = MARGIN + STACK_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width, height, width, height
+    );
+    svg.push_str(&format!(
+        "<line x1=\"{m}\" y1=\"{m}\" x2=\"{m}\" y2=\"{b}\" stroke=\"black\"/>\n",
+        m = MARGIN,
+        b = baseline
+    ));
+    for bits in 0..=maximum.floor() as usize {
+        let y = baseline - bits as f64 * scale;
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"end\">{}</text>\n",
+            MARGIN - 4.0,
+            y + 3.0,
+            bits
+        ));
+    }
+    svg.push_str(&format!(
+        "<text x=\"12\" y=\"{}\" font-family=\"sans-serif\" font-size=\"12\" text-anchor=\"middle\" transform=\"rotate(-90 12 {})\">bits</text>\n",
+        MARGIN + STACK_HEIGHT / 2.0,
+        MARGIN + STACK_HEIGHT / 2.0
+    ));
+
+    for (column, heights) in letter_heights(profile).iter().enumerate() {
+        let x = MARGIN + column as f64 * COLUMN_WIDTH;
+        let mut stack: Vec<(T, f64)> = T::RESIDUES
+            .iter()
+            .zip(heights)
+            .map(|(&symbol, &bits)| (symbol, bits * scale))
+            .filter(|&(_, height)| height > 0.0)
+            .collect();
+        // Draw from the bottom up, leaving the tallest letter on top
+        stack.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let mut y = baseline;
+        for (symbol, height) in stack {
+            let letter = symbol.to_string();
+            let colour = scheme.colour(letter.chars().next().unwrap_or(' '));
+            svg.push_str(&format!(
+                "<text transform=\"translate({:.2} {:.2}) scale({:.4} {:.4})\" font-family=\"sans-serif\" font-weight=\"bold\" font-size=\"100\" text-anchor=\"middle\" fill=\"{}\">{}</text>\n",
+                x + COLUMN_WIDTH / 2.0,
+                y,
+                COLUMN_WIDTH / (100.0 * LETTER_WIDTH),
+                height / (100.0 * CAP_HEIGHT),
+                colour,
+                letter
+            ));
+            y -= height;
+        }
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"middle\">{}</text>\n",
+            x + COLUMN_WIDTH / 2.0,
+            baseline + 14.0,
+            column + 1
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::{Dna, DnaBase};
+    use crate::utils::fasta::Fasta;
+    use crate::utils::protein::AminoAcid;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn conserved_columns_fill_the_stack() {
+        let profile: Profile<DnaBase> =
+            Profile::from_fasta(&Fasta::<Dna>::try_from(">a\nAC\n>b\nAG\n>c\nAT\n>d\nAA").unwrap())
+                .unwrap();
+        let heights = letter_heights(&profile);
+        assert_eq!(heights[0], vec![2.0, 0.0, 0.0, 0.0]);
+        assert_eq!(heights[1], vec![0.0; 4]);
+
+        let svg = svg(&profile, ColourScheme::Nucleotide);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"140\""));
+        // Only the conserved A is drawn, filling the full height
+        assert_eq!(svg.matches("font-size=\"100\"").count(), 1);
+        assert!(svg.contains("scale(0.4545 2.0833)\" font-family=\"sans-serif\" font-weight=\"bold\" font-size=\"100\" text-anchor=\"middle\" fill=\"#109648\">A</text>"));
+        assert_eq!(
+            ColourScheme::named("Chemistry").map(|scheme| scheme.colour('D')),
+            Ok("#d62839")
+        )
+    }
+
+    #[test]
+    fn conserved_protein_columns_fill_the_stack() {
+        use AminoAcid::{Stop, K, M};
+        // Stop codons are left out of the alphabet like gaps
+        let profile = Profile::from_sequences(&[&[M, K][..], &[M, Stop][..], &[M, K][..]]).unwrap();
+        let heights = letter_heights(&profile);
+        assert_eq!(heights[0].len(), 20);
+        assert_eq!(heights[0][M.index()], 20f64.log2());
+        assert!((heights[1][K.index()] - 20f64.log2() * 2.0 / 3.0).abs() < 1e-12);
+
+        let svg = svg(&profile, ColourScheme::Chemistry);
+        assert_eq!(svg.matches("scale(0.4545 2.0833)").count(), 1);
+        // The axis stops at the largest whole number of bits below log2(20)
+        assert!(svg.contains(">4</text>") && !svg.contains(">5</text>"))
+    }
+    #[test]
+    fn gappy_columns_are_scaled_down() {
+        use crate::utils::msa::{Format, Msa};
+        let msa = Msa::parse(">a\nAA\n>b\nA-\n>c\nA-\n>d\nA-", Format::Fasta).unwrap();
+        let heights = letter_heights(&Profile::<DnaBase>::from_msa(&msa));
+        assert_eq!(heights[0], vec![2.0, 0.0, 0.0, 0.0]);
+        // A single base among gaps is conserved but only a quarter as tall
+        assert_eq!(heights[1], vec![0.5, 0.0, 0.0, 0.0])
+    }
+}
@@ -0,0 +1,175 @@
+use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::fasta::Fasta;
+use crate::utils::kmer::{max_k, KmerCodes};
+use std::collections::BTreeSet;
+
+/// The MurmurHash3 64-bit finaliser, used to spread k-mer encodings uniformly over `u64`
+fn hash(code: u64) -> u64 {
+    let mut h = code;
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    h
+}
+
+/// A bottom-s MinHash sketch: the `size` smallest hashes of a sequence's canonical k-mers
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sketch {
+    k: usize,
+    size: usize,
+    hashes: Vec<u64>,
+}
+
+impl Sketch {
+    /// Sketch the canonical k-mers of `dna`. Panics unless `k` is between 1
+    /// and `max_k()` and `size` is positive.
+    pub fn new(dna: &Dna, k: usize, size: usize) -> Self {
+        assert!(
+            k > 0 && k <= max_k::<DnaBase>(),
+            "k must be positive and small enough to encode k-mers in 64 bits"
+        );
+        assert!(size > 0, "Sketches must keep at least one hash");
+        let mut smallest = BTreeSet::new();
+        for code in KmerCodes::new(dna.sequence(), k, Some(DnaBase::complement)) {
+            let h = hash(code);
+            if smallest.len() < size {
+                smallest.insert(h);
+            } else if h < *smallest.iter().next_back().unwrap() && smallest.insert(h) {
+                let largest = *smallest.iter().next_back().unwrap();
+                smallest.remove(&largest);
+            }
+        }
+        Sketch {
+            k,
+            size,
+            hashes: smallest.into_iter().collect(),
+        }
+    }
+
+    /// Sketch every record of the fasta, keeping the record names
+    pub fn from_fasta(fasta: &Fasta<Dna>, k: usize, size: usize) -> Vec<(String, Sketch)> {
+        fasta
+            .iter()
+            .map(|(name, dna)| (name.to_string(), Sketch::new(dna, k, size)))
+            .collect()
+    }
+
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    fn check_compatible(&self, other: &Sketch) {
+        assert_eq!(self.k, other.k, "Sketches must use the same k");
+    }
+
+    /// Estimate the Jaccard index of the two k-mer sets from the bottom
+    /// hashes of their union
+    pub fn jaccard(&self, other: &Sketch) -> f64 {
+        self.check_compatible(other);
+        let size = self.size.min(other.size);
+        let (mut i, mut j) = (0, 0);
+        let (mut shared, mut union) = (0, 0);
+        while union < size && (i < self.hashes.len() || j < other.hashes.len()) {
+            match (self.hashes.get(i), other.hashes.get(j)) {
+                (Some(a), Some(b)) if a == b => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+                (Some(a), Some(b)) if a < b => i += 1,
+                (Some(_), None) => i += 1,
+                _ => j += 1,
+            }
+            union += 1;
+        }
+        if union == 0 {
+            return 0.;
+        }
+        shared as f64 / union as f64
+    }
+
+    /// The Mash distance, estimating the per-base mutation rate between the sequences
+    pub fn mash_distance(&self, other: &Sketch) -> f64 {
+        let jaccard = self.jaccard(other);
+        if jaccard == 0. {
+            return 1.;
+        }
+        if jaccard == 1. {
+            return 0.;
+        }
+        (-(2. * jaccard / (1. + jaccard)).ln() / self.k as f64).min(1.)
+    }
+
+    /// Estimate the fraction of this sequence's k-mers that also occur in `other`
+    pub fn containment(&self, other: &Sketch) -> f64 {
+        self.check_compatible(other);
+        // Only hashes within the range covered by the other sketch can be compared
+        let limit = if other.hashes.len() < other.size {
+            u64::MAX
+        } else {
+            *other.hashes.last().unwrap()
+        };
+        let comparable: Vec<&u64> = self.hashes.iter().filter(|&&h| h <= limit).collect();
+        if comparable.is_empty() {
+            return 0.;
+        }
+        let shared = comparable
+            .iter()
+            .filter(|h| other.hashes.binary_search(h).is_ok())
+            .count();
+        shared as f64 / comparable.len() as f64
+    }
+}
+
+/// Pairwise Mash distances between every pair of sketches
+pub fn distance_matrix(sketches: &[Sketch]) -> Vec<Vec<f64>> {
+    sketches
+        .iter()
+        .map(|a| sketches.iter().map(|b| a.mash_distance(b)).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn identical_sequences_have_zero_distance() {
+        let dna = Dna::try_from("ACGTTGCATGTCGCATGATGCATGAGAGCTACGGATCGATCGGGCTAGCATCGAT").unwrap();
+        let a = Sketch::new(&dna, 5, 20);
+        let mut reverse = dna.clone();
+        reverse.reverse_complement();
+        let b = Sketch::new(&reverse, 5, 20);
+        assert_eq!(a, b);
+        assert_eq!(a.jaccard(&b), 1.);
+        assert_eq!(a.mash_distance(&b), 0.);
+        assert_eq!(a.containment(&b), 1.)
+    }
+
+    #[test]
+    fn unsaturated_sketch_gives_exact_jaccard() {
+        let a = Sketch::new(&Dna::try_from("AAAACCCC").unwrap(), 4, 100);
+        let b = Sketch::new(&Dna::try_from("AAAACCCG").unwrap(), 4, 100);
+        // The sequences share 4 of their 6 distinct canonical 4-mers
+        assert_eq!(a.jaccard(&b), 4. / 6.);
+        assert_eq!(a.containment(&b), 4. / 5.)
+    }
+
+    #[test]
+    fn distance_matrix_is_symmetric() {
+        let sketches: Vec<Sketch> = ["ACGTACGTTTGACCA", "ACGTACGATTGACCA", "GGGGCCCCAAATTTG"]
+            .iter()
+            .map(|s| Sketch::new(&Dna::try_from(*s).unwrap(), 3, 10))
+            .collect();
+        let matrix = distance_matrix(&sketches);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0.);
+            for (j, distance) in row.iter().enumerate() {
+                assert_eq!(*distance, matrix[j][i]);
+            }
+        }
+    }
+}
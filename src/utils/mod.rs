@@ -1,9 +1,28 @@
+pub mod alignment;
+pub mod approximate_match;
 pub mod digraph;
 pub mod dna;
 pub mod fasta;
+pub mod fm_index;
+pub mod gapped;
+pub mod graph_format;
+pub mod kmer;
+pub mod logo;
+pub mod minhash;
+pub mod motif;
+pub mod msa;
+pub mod overlap;
+pub mod profile;
 pub mod protein;
+pub mod random;
 pub mod rna;
+pub mod satisfiability;
 pub mod sequence;
+pub mod shortest_path;
+pub mod subsequence;
+pub mod suffix_tree;
+pub mod traversal;
+pub mod trie;
 pub mod uniprot;
 
 pub fn read_file(filename: &str) -> String {
@@ -0,0 +1,426 @@
+use crate::utils::approximate_match::{hamming_search, neighbours};
+use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::fasta::Fasta;
+use crate::utils::kmer::decode;
+use crate::utils::profile::Profile;
+use crate::utils::random::Rng;
+use crate::utils::sequence::{Alphabet, Sequence};
+use std::collections::BTreeSet;
+
+/// One k-mer chosen from each of a set of sequences, scored by the number of
+/// symbols differing from the consensus of their column. Lower is better.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MotifSet {
+    motifs: Vec<Dna>,
+    score: usize,
+}
+
+impl MotifSet {
+    pub fn new(motifs: Vec<Vec<DnaBase>>) -> Self {
+        let score = score(&motifs.iter().map(Vec::as_slice).collect::<Vec<_>>());
+        MotifSet {
+            motifs: motifs.into_iter().map(Dna::from).collect(),
+            score,
+        }
+    }
+
+    pub fn motifs(&self) -> &[Dna] {
+        &self.motifs
+    }
+
+    pub fn score(&self) -> usize {
+        self.score
+    }
+}
+
+fn score(motifs: &[&[DnaBase]]) -> usize {
+    let profile = Profile::from_sequences(motifs).unwrap();
+    profile
+        .consensus()
+        .iter()
+        .enumerate()
+        .map(|(column, &base)| motifs.len() - base.map_or(0, |base| profile.count(column, base)))
+        .sum()
+}
+
+/// Symbol frequencies of some motifs, adding `pseudocount` to every count
+fn frequencies(motifs: &[&[DnaBase]], pseudocount: f64) -> Vec<Vec<f64>> {
+    Profile::from_sequences(motifs)
+        .unwrap()
+        .with_pseudocount(pseudocount)
+        .frequencies()
+}
+
+/// The probability of `kmer` being generated by a profile
+fn probability(kmer: &[DnaBase], frequencies: &[Vec<f64>]) -> f64 {
+    kmer.iter()
+        .zip(frequencies)
+        .map(|(base, column)| column[base.index()])
+        .product()
+}
+
+/// The first k-mer of `sequence` most likely to be generated by a profile
+/// with the given frequencies, where `k` is the length of the profile.
+/// Fails if the profile is empty or longer than the sequence.
+pub fn profile_most_probable<'a>(
+    sequence: &'a [DnaBase],
+    frequencies: &[Vec<f64>],
+) -> Result<&'a [DnaBase], String> {
+    let k = frequencies.len();
+    if k == 0 {
+        return Err("The profile is empty".to_string());
+    }
+    if sequence.len() < k {
+        return Err(format!(
+            "The sequence is shorter than the profile length {}",
+            k
+        ));
+    }
+    let mut best = (-1.0, &sequence[..k]);
+    for kmer in sequence.windows(k) {
+        let p = probability(kmer, frequencies);
+        if p > best.0 {
+            best = (p, kmer);
+        }
+    }
+    Ok(best.1)
+}
+
+/// The symbols of each region, checking there is at least one region and
+/// that every region has a k-mer
+fn regions_symbols(regions: &Fasta<Dna>, k: usize) -> Result<Vec<&[DnaBase]>, String> {
+    if k == 0 {
+        return Err("k must be at least 1".to_string());
+    }
+    let symbols = regions
+        .iter()
+        .map(|(name, dna)| {
+            if dna.len() < k {
+                Err(format!("Region {} is shorter than k = {}", name, k))
+            } else {
+                Ok(dna.symbols())
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if symbols.is_empty() {
+        return Err("There are no regions to search".to_string());
+    }
+    Ok(symbols)
+}
+
+/// Every k-mer occurring with at most `d` mismatches in all of the regions,
+/// in lexicographic order. The time taken grows quickly with `k` and `d`.
+pub fn motif_enumeration(regions: &Fasta<Dna>, k: usize, d: usize) -> Result<Vec<Dna>, String> {
+    let regions = regions_symbols(regions, k)?;
+    let mut candidates = BTreeSet::new();
+    for region in &regions {
+        for window in region.windows(k) {
+            candidates.extend(neighbours(window, d));
+        }
+    }
+    Ok(candidates
+        .into_iter()
+        .filter(|pattern| {
+            regions
+                .iter()
+                .all(|region| !hamming_search(region, pattern, d).is_empty())
+        })
+        .map(Dna::from)
+        .collect())
+}
+
+/// The smallest number of mismatches between `pattern` and a k-mer of `region`
+fn distance(pattern: &[DnaBase], region: &[DnaBase]) -> usize {
+    region
+        .windows(pattern.len())
+        .map(|window| window.iter().zip(pattern).filter(|(a, b)| a != b).count())
+        .min()
+        .unwrap_or(pattern.len())
+}
+
+/// The k-mer minimising the total distance to the closest k-mer of each
+/// region, taking the first in lexicographic order on ties, with that total.
+/// Every possible k-mer is tried, so `k` must be small.
+pub fn median_string(regions: &Fasta<Dna>, k: usize) -> Result<(Dna, usize), String> {
+    let regions = regions_symbols(regions, k)?;
+    let (code, total) = (0..4u64.pow(k as u32))
+        .map(|code| {
+            let pattern: Vec<DnaBase> = decode(code, k);
+            let total = regions
+                .iter()
+                .map(|region| distance(&pattern, region))
+                .sum();
+            (code, total)
+        })
+        .fold((0, usize::MAX), |best, candidate| {
+            if candidate.1 < best.1 {
+                candidate
+            } else {
+                best
+            }
+        });
+    Ok((Dna::from(decode::<DnaBase>(code, k)), total))
+}
+
+/// Greedy motif search: each k-mer of the first region is extended by
+/// choosing the profile-most probable k-mer of each following region, using
+/// a profile of the motifs chosen so far with `pseudocount` added to each
+/// count. A pseudocount of 1 gives Laplace's rule of succession.
+pub fn greedy_motif_search(
+    regions: &Fasta<Dna>,
+    k: usize,
+    pseudocount: f64,
+) -> Result<MotifSet, String> {
+    let regions = regions_symbols(regions, k)?;
+    let mut best: Vec<&[DnaBase]> = regions.iter().map(|region| &region[..k]).collect();
+    let mut best_score = score(&best);
+    for kmer in regions[0].windows(k) {
+        let mut motifs = vec![kmer];
+        for region in &regions[1..] {
+            let profile = frequencies(&motifs, pseudocount);
+            motifs.push(profile_most_probable(region, &profile)?);
+        }
+        let motifs_score = score(&motifs);
+        if motifs_score < best_score {
+            best = motifs;
+            best_score = motifs_score;
+        }
+    }
+    Ok(MotifSet::new(
+        best.iter().map(|motif| motif.to_vec()).collect(),
+    ))
+}
+
+fn random_motifs<'a>(regions: &[&'a [DnaBase]], k: usize, rng: &mut Rng) -> Vec<&'a [DnaBase]> {
+    regions
+        .iter()
+        .map(|region| {
+            let start = rng.below(region.len() - k + 1);
+            &region[start..start + k]
+        })
+        .collect()
+}
+
+/// Randomized motif search: from random motifs, repeatedly replace them by
+/// the profile-most probable k-mers of their own profile until the score
+/// stops improving, keeping the best of `restarts` runs
+pub fn randomized_motif_search(
+    regions: &Fasta<Dna>,
+    k: usize,
+    restarts: usize,
+    rng: &mut Rng,
+) -> Result<MotifSet, String> {
+    let regions = regions_symbols(regions, k)?;
+    if restarts == 0 {
+        return Err("Motif search needs at least one restart".to_string());
+    }
+    let mut best: Option<(usize, Vec<&[DnaBase]>)> = None;
+    for _ in 0..restarts {
+        let mut motifs = random_motifs(&regions, k, rng);
+        let mut motifs_score = score(&motifs);
+        loop {
+            let profile = frequencies(&motifs, 1.0);
+            let next: Vec<&[DnaBase]> = regions
+                .iter()
+                .map(|region| profile_most_probable(region, &profile))
+                .collect::<Result<_, _>>()?;
+            let next_score = score(&next);
+            if next_score >= motifs_score {
+                break;
+            }
+            motifs = next;
+            motifs_score = next_score;
+        }
+        if best
+            .as_ref()
+            .map_or(true, |(best_score, _)| motifs_score < *best_score)
+        {
+            best = Some((motifs_score, motifs));
+        }
+    }
+    let (_, motifs) = best.expect("At least one restart was run");
+    Ok(MotifSet::new(
+        motifs.iter().map(|motif| motif.to_vec()).collect(),
+    ))
+}
+
+/// Gibbs sampling: from random motifs, repeatedly replace the motif of one
+/// random region by a k-mer drawn with probability given by the profile of
+/// the other motifs, keeping the best motifs seen over `restarts` runs of
+/// `iterations` steps
+pub fn gibbs_sampler(
+    regions: &Fasta<Dna>,
+    k: usize,
+    iterations: usize,
+    restarts: usize,
+    rng: &mut Rng,
+) -> Result<MotifSet, String> {
+    let regions = regions_symbols(regions, k)?;
+    if restarts == 0 {
+        return Err("Motif search needs at least one restart".to_string());
+    }
+    let mut best: Option<(usize, Vec<&[DnaBase]>)> = None;
+    for _ in 0..restarts {
+        let mut motifs = random_motifs(&regions, k, rng);
+        let mut run_best = (score(&motifs), motifs.clone());
+        for _ in 0..iterations {
+            let i = rng.below(regions.len());
+            let others: Vec<&[DnaBase]> = motifs
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &motif)| motif)
+                .collect();
+            let profile = frequencies(&others, 1.0);
+            let weights: Vec<f64> = regions[i]
+                .windows(k)
+                .map(|kmer| probability(kmer, &profile))
+                .collect();
+            let start = rng.weighted(&weights);
+            motifs[i] = &regions[i][start..start + k];
+            let motifs_score = score(&motifs);
+            if motifs_score < run_best.0 {
+                run_best = (motifs_score, motifs.clone());
+            }
+        }
+        if best
+            .as_ref()
+            .map_or(true, |(best_score, _)| run_best.0 < *best_score)
+        {
+            best = Some(run_best);
+        }
+    }
+    let (_, motifs) = best.expect("At least one restart was run");
+    Ok(MotifSet::new(
+        motifs.iter().map(|motif| motif.to_vec()).collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn regions(sequences: &[&str]) -> Fasta<Dna> {
+        Fasta::from(
+            sequences
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (format!("region_{}", i + 1), Dna::try_from(*s).unwrap()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn motif_strings(motifs: &MotifSet) -> Vec<String> {
+        motifs.motifs().iter().map(|dna| dna.to_string()).collect()
+    }
+
+    #[test]
+    fn enumeration_and_median_string() {
+        let enumerated = motif_enumeration(
+            &regions(&["ATTTGGC", "TGCCTTA", "CGGTATC", "GAAAATT"]),
+            3,
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            enumerated
+                .iter()
+                .map(|dna| dna.to_string())
+                .collect::<Vec<_>>(),
+            vec!["ATA", "ATT", "GTT", "TTT"]
+        );
+        let (median, total) = median_string(
+            &regions(&[
+                "AAATTGACGCAT",
+                "GACGACCACGTT",
+                "CGTCAGCGCCTG",
+                "GCTGAGCACCGG",
+                "AGTTCGGGACAG",
+            ]),
+            3,
+        )
+        .unwrap();
+        assert_eq!((median.to_string(), total), ("GAC".to_string(), 2))
+    }
+
+    #[test]
+    fn greedy_search_with_and_without_pseudocounts() {
+        let regions = regions(&[
+            "GGCGTTCAGGCA",
+            "AAGAATCAGTCA",
+            "CAAGGAGTTCGC",
+            "CACGTCAATCAC",
+            "CAATAATATTCG",
+        ]);
+        assert_eq!(
+            motif_strings(&greedy_motif_search(&regions, 3, 0.0).unwrap()),
+            vec!["CAG", "CAG", "CAA", "CAA", "CAA"]
+        );
+        assert_eq!(
+            motif_strings(&greedy_motif_search(&regions, 3, 1.0).unwrap()),
+            vec!["TTC", "ATC", "TTC", "ATC", "TTC"]
+        )
+    }
+
+    #[test]
+    fn randomized_searches_find_planted_motif() {
+        let regions = regions(&[
+            "CGCCCCTCTCGGGGGTGTTCAGTAAACGGCCA",
+            "GGGCGAGGTATGTGTAAGTGCCAAGGTGCCAG",
+            "TAGTACCGAGACCGAAAGAAGTATACAGGCGT",
+            "TAGATCAAGTTTCAGGTGCACGTCGGTGAACC",
+            "AATCCACCAGCTCCACGTGCAATGTTGGCCTA",
+        ]);
+        let expected = MotifSet::new(
+            ["TCTCGGGG", "CCAAGGTG", "TACAGGCG", "TTCAGGTG", "TCCACGTG"]
+                .iter()
+                .map(|s| Dna::try_from(*s).unwrap().symbols().to_vec())
+                .collect(),
+        );
+        let mut rng = Rng::new(1);
+        let randomized = randomized_motif_search(&regions, 8, 1000, &mut rng).unwrap();
+        assert!(randomized.score() <= expected.score());
+        let gibbs = gibbs_sampler(&regions, 8, 200, 50, &mut rng).unwrap();
+        assert!(gibbs.score() <= expected.score());
+        // The same seed gives the same motifs
+        assert_eq!(
+            randomized_motif_search(&regions, 8, 10, &mut Rng::new(5)).unwrap(),
+            randomized_motif_search(&regions, 8, 10, &mut Rng::new(5)).unwrap()
+        )
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        let short = regions(&["ACGTACGT", "ACG"]);
+        let empty = regions(&[]);
+        let mut rng = Rng::new(1);
+        for &(input, k, message) in &[
+            (&short, 0, "k must be at least 1"),
+            (&short, 4, "Region region_2 is shorter than k = 4"),
+            (&empty, 3, "There are no regions to search"),
+        ] {
+            let expected = Some(message.to_string());
+            assert_eq!(motif_enumeration(input, k, 1).err(), expected);
+            assert_eq!(median_string(input, k).err(), expected);
+            assert_eq!(greedy_motif_search(input, k, 1.0).err(), expected);
+            assert_eq!(
+                randomized_motif_search(input, k, 10, &mut rng).err(),
+                expected
+            );
+            assert_eq!(gibbs_sampler(input, k, 10, 10, &mut rng).err(), expected);
+        }
+        let restarts = Err("Motif search needs at least one restart".to_string());
+        assert_eq!(randomized_motif_search(&short, 3, 0, &mut rng), restarts);
+        assert_eq!(gibbs_sampler(&short, 3, 10, 0, &mut rng), restarts);
+        let sequence = Dna::try_from("ACG").unwrap();
+        assert_eq!(
+            profile_most_probable(sequence.symbols(), &[]),
+            Err("The profile is empty".to_string())
+        );
+        assert_eq!(
+            profile_most_probable(sequence.symbols(), &vec![vec![0.25; 4]; 4]),
+            Err("The sequence is shorter than the profile length 4".to_string())
+        )
+    }
+}
@@ -0,0 +1,427 @@
+use crate::utils::fasta::Fasta;
+use crate::utils::gapped::GappedSequence;
+use std::convert::TryFrom;
+use std::fmt::Display;
+
+/// Columns per block when writing interleaved Clustal alignments
+const CLUSTAL_WIDTH: usize = 60;
+
+/// Multiple sequence alignment file formats
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Format {
+    /// FASTA records with `-` for gaps
+    Fasta,
+    Clustal,
+    Stockholm,
+    /// Relaxed PHYLIP, where names end at the first whitespace
+    Phylip,
+}
+
+impl Format {
+    /// A format looked up by name ignoring case
+    pub fn named(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "fasta" | "fa" => Ok(Format::Fasta),
+            "clustal" | "aln" => Ok(Format::Clustal),
+            "stockholm" | "sto" => Ok(Format::Stockholm),
+            "phylip" | "phy" => Ok(Format::Phylip),
+            _ => Err(format!("Unknown alignment format: {}", name)),
+        }
+    }
+}
+
+/// A multiple sequence alignment: named rows which are all the same length
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Msa<T> {
+    rows: Vec<(String, GappedSequence<T>)>,
+}
+
+impl<T: Copy> Msa<T> {
+    pub fn new(rows: Vec<(String, GappedSequence<T>)>) -> Result<Self, String> {
+        if let Some((_, first)) = rows.first() {
+            if let Some((id, _)) = rows.iter().find(|(_, row)| row.len() != first.len()) {
+                return Err(format!(
+                    "Row {} has a different length to the first row",
+                    id
+                ));
+            }
+        }
+        Ok(Msa { rows })
+    }
+
+    pub fn rows(&self) -> &[(String, GappedSequence<T>)] {
+        &self.rows
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The number of columns
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |(_, row)| row.len())
+    }
+
+    pub fn column(&self, i: usize) -> Vec<Option<T>> {
+        self.rows.iter().map(|(_, row)| row.symbols()[i]).collect()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Vec<Option<T>>> + '_ {
+        (0..self.width()).map(move |i| self.column(i))
+    }
+
+    /// The number of gaps in each column
+    pub fn column_gaps(&self) -> Vec<usize> {
+        self.columns()
+            .map(|column| column.iter().filter(|symbol| symbol.is_none()).count())
+            .collect()
+    }
+
+    /// The number of gaps in each row
+    pub fn row_gaps(&self) -> Vec<usize> {
+        self.rows
+            .iter()
+            .map(|(_, row)| {
+                row.symbols()
+                    .iter()
+                    .filter(|symbol| symbol.is_none())
+                    .count()
+            })
+            .collect()
+    }
+
+    /// The fraction of all positions which are gaps
+    pub fn gap_fraction(&self) -> f64 {
+        let cells = self.row_count() * self.width();
+        if cells == 0 {
+            0.0
+        } else {
+            self.row_gaps().iter().sum::<usize>() as f64 / cells as f64
+        }
+    }
+
+    /// The number of columns without any gaps
+    pub fn gapless_columns(&self) -> usize {
+        self.column_gaps().iter().filter(|&&gaps| gaps == 0).count()
+    }
+}
+
+impl<T> Msa<T>
+where
+    T: Copy + TryFrom<char>,
+    T::Error: ToString,
+{
+    pub fn parse(s: &str, format: Format) -> Result<Self, String> {
+        let rows = match format {
+            Format::Fasta => Fasta::<String>::try_from(s)?.into_iter().collect(),
+            Format::Clustal => parse_clustal(s)?,
+            Format::Stockholm => parse_stockholm(s)?,
+            Format::Phylip => parse_phylip(s)?,
+        };
+        let rows = rows
+            .into_iter()
+            .map(|(id, row)| Ok((id, GappedSequence::try_from(row)?)))
+            .collect::<Result<_, String>>()?;
+        Self::new(rows)
+    }
+}
+
+impl<T: Copy + Eq + Display> Msa<T> {
+    /// Write the alignment in `format`. FASTA keeps the whole of each id, but
+    /// the other formats end names at whitespace, so ids are cut at their
+    /// first whitespace and must still differ from each other.
+    pub fn write(&self, format: Format) -> Result<String, String> {
+        let rows: Vec<(&str, String)> = self
+            .rows
+            .iter()
+            .map(|(id, row)| {
+                let name = match format {
+                    Format::Fasta => id.as_str(),
+                    _ => id.split_whitespace().next().unwrap_or(""),
+                };
+                (name, row.to_string())
+            })
+            .collect();
+        if format != Format::Fasta {
+            let mut names = std::collections::HashSet::new();
+            if let Some((name, _)) = rows.iter().find(|(name, _)| !names.insert(*name)) {
+                return Err(format!("Name {} is used by more than one row", name));
+            }
+        }
+        let name_width = rows.iter().map(|(id, _)| id.len()).max().unwrap_or(0);
+        let mut output = String::new();
+        match format {
+            Format::Fasta => {
+                for (id, row) in &rows {
+                    output.push_str(&format!(">{}\n{}\n", id, row));
+                }
+            }
+            Format::Clustal => {
+                output.push_str("CLUSTAL W multiple sequence alignment\n");
+                for start in (0..self.width()).step_by(CLUSTAL_WIDTH) {
+                    let end = (start + CLUSTAL_WIDTH).min(self.width());
+                    output.push('\n');
+                    for (id, row) in &rows {
+                        output.push_str(&format!(
+                            "{:width$}    {}\n",
+                            id,
+                            row.chars()
+                                .skip(start)
+                                .take(end - start)
+                                .collect::<String>(),
+                            width = name_width
+                        ));
+                    }
+                    // Fully conserved columns are starred
+                    let conservation: String = (start..end)
+                        .map(|i| {
+                            let column = self.column(i);
+                            if column[0].is_some() && column.iter().all(|&s| s == column[0]) {
+                                '*'
+                            } else {
+                                ' '
+                            }
+                        })
+                        .collect();
+                    output.push_str(&format!(
+                        "{:width$}    {}\n",
+                        "",
+                        conservation.trim_end(),
+                        width = name_width
+                    ));
+                }
+            }
+            Format::Stockholm => {
+                output.push_str("# STOCKHOLM 1.0\n");
+                for (id, row) in &rows {
+                    output.push_str(&format!("{:width$}  {}\n", id, row, width = name_width));
+                }
+                output.push_str("//\n");
+            }
+            Format::Phylip => {
+                output.push_str(&format!("{} {}\n", self.row_count(), self.width()));
+                for (id, row) in &rows {
+                    output.push_str(&format!("{:width$}  {}\n", id, row, width = name_width));
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl<T: Copy> TryFrom<Fasta<GappedSequence<T>>> for Msa<T> {
+    type Error = String;
+
+    fn try_from(fasta: Fasta<GappedSequence<T>>) -> Result<Self, Self::Error> {
+        Self::new(fasta.into_iter().collect())
+    }
+}
+
+impl<T> From<Msa<T>> for Fasta<GappedSequence<T>> {
+    fn from(msa: Msa<T>) -> Self {
+        Fasta::from(msa.rows)
+    }
+}
+
+/// Append `sequence` to the row named `id`, adding the row if it is new, for
+/// formats which split long alignments into blocks
+fn append(rows: &mut Vec<(String, String)>, id: &str, sequence: &str) {
+    match rows.iter_mut().find(|(row_id, _)| row_id == id) {
+        Some((_, row)) => row.push_str(sequence),
+        None => rows.push((id.to_string(), sequence.to_string())),
+    }
+}
+
+/// Split a `name sequence` line, dropping any trailing residue count
+fn name_and_sequence(line: &str) -> Result<(&str, String), String> {
+    let mut fields = line.split_whitespace();
+    match (fields.next(), fields.next()) {
+        (Some(name), Some(sequence)) => Ok((name, sequence.to_string())),
+        _ => Err(format!("Expected a name and sequence: {}", line)),
+    }
+}
+
+fn parse_clustal(s: &str) -> Result<Vec<(String, String)>, String> {
+    let mut lines = s.lines();
+    match lines.next() {
+        Some(header) if header.starts_with("CLUSTAL") || header.starts_with("MUSCLE") => {}
+        _ => return Err("Missing CLUSTAL header".to_string()),
+    }
+    let mut rows = Vec::new();
+    // Conservation lines start with whitespace
+    for line in lines.filter(|line| !line.trim().is_empty() && !line.starts_with(' ')) {
+        let (name, sequence) = name_and_sequence(line)?;
+        append(&mut rows, name, &sequence);
+    }
+    Ok(rows)
+}
+
+fn parse_stockholm(s: &str) -> Result<Vec<(String, String)>, String> {
+    let mut lines = s.lines().map(str::trim);
+    match lines.next() {
+        Some(header) if header.starts_with("# STOCKHOLM") => {}
+        _ => return Err("Missing STOCKHOLM header".to_string()),
+    }
+    let mut rows = Vec::new();
+    for line in lines {
+        if line == "//" {
+            return Ok(rows);
+        }
+        // Skip markup such as #=GF and #=GC lines
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, sequence) = name_and_sequence(line)?;
+        append(&mut rows, name, &sequence);
+    }
+    Err("Missing // at the end of the alignment".to_string())
+}
+
+/// Parse relaxed PHYLIP, either sequential with each row on one line or
+/// interleaved, where blocks after the first hold sequence without names
+fn parse_phylip(s: &str) -> Result<Vec<(String, String)>, String> {
+    let mut lines = s.lines().filter(|line| !line.trim().is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| "Missing PHYLIP header".to_string())?;
+    let counts = header
+        .split_whitespace()
+        .map(|count| count.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("Invalid PHYLIP header: {}", header))?;
+    let (row_count, width) = match counts[..] {
+        [row_count, width] => (row_count, width),
+        _ => return Err(format!("Invalid PHYLIP header: {}", header)),
+    };
+
+    let mut rows: Vec<(String, String)> = Vec::with_capacity(row_count);
+    for (i, line) in lines.enumerate() {
+        if i < row_count {
+            let mut fields = line.split_whitespace();
+            let name = fields.next().unwrap().to_string();
+            rows.push((name, fields.collect()));
+        } else if row_count > 0 {
+            rows[i % row_count].1.extend(line.split_whitespace());
+        }
+    }
+    if rows.len() != row_count {
+        return Err(format!("Expected {} rows, found {}", row_count, rows.len()));
+    }
+    if let Some((id, _)) = rows.iter().find(|(_, row)| row.chars().count() != width) {
+        return Err(format!("Row {} is not {} columns long", id, width));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::DnaBase;
+
+    fn msa() -> Msa<DnaBase> {
+        Msa::parse(">a\nACGT-A\n>b\nAC-TTA\n>c\nA--TCA", Format::Fasta).unwrap()
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        let msa = msa();
+        for &format in &[
+            Format::Fasta,
+            Format::Clustal,
+            Format::Stockholm,
+            Format::Phylip,
+        ] {
+            assert_eq!(
+                Msa::parse(&msa.write(format).unwrap(), format),
+                Ok(msa.clone())
+            );
+        }
+        assert_eq!(
+            msa.write(Format::Clustal).unwrap(),
+            "CLUSTAL W multiple sequence alignment
+
+a    ACGT-A
+b    AC-TTA
+c    A--TCA
+     *  * *
+"
+        );
+        assert_eq!(
+            msa.write(Format::Phylip).unwrap(),
+            "3 6\na  ACGT-A\nb  AC-TTA\nc  A--TCA\n"
+        )
+    }
+
+    #[test]
+    fn names_end_at_whitespace() {
+        let described = Msa::<char>::parse(
+            ">sp|P1 human\nMEAN-LY\n>sp|P2 mouse\nMEANL-Y",
+            Format::Fasta,
+        )
+        .unwrap();
+        let short = Msa::<char>::parse(">sp|P1\nMEAN-LY\n>sp|P2\nMEANL-Y", Format::Fasta).unwrap();
+        for &format in &[Format::Clustal, Format::Stockholm, Format::Phylip] {
+            let written = described.write(format).unwrap();
+            assert_eq!(Msa::parse(&written, format), Ok(short.clone()));
+        }
+        assert_eq!(
+            Msa::parse(&described.write(Format::Fasta).unwrap(), Format::Fasta),
+            Ok(described)
+        );
+        let clash = Msa::<char>::parse(
+            ">sp|P1 human\nMEAN-LY\n>sp|P1 mouse\nMEANL-Y",
+            Format::Fasta,
+        )
+        .unwrap();
+        assert_eq!(
+            clash.write(Format::Phylip),
+            Err("Name sp|P1 is used by more than one row".to_string())
+        );
+        assert!(clash.write(Format::Fasta).is_ok())
+    }
+
+    #[test]
+    fn interleaved_blocks_are_joined() {
+        let clustal = "CLUSTAL O(1.2.4) multiple sequence alignment
+
+a      ACG 3
+b      A-G 2
+       * *
+
+a      T-A 5
+b      TTA 5
+       * *
+";
+        let stockholm = "# STOCKHOLM 1.0
+#=GF ID example
+a ACG
+b A.G
+
+a T-A
+b TTA
+#=GC SS_cons ......
+//
+";
+        let phylip = "2 6\na ACG\nb A-G\nT-A\nTTA\n";
+        let expected: Msa<char> = Msa::parse(">a\nACGT-A\n>b\nA-GTTA", Format::Fasta).unwrap();
+        assert_eq!(Msa::parse(clustal, Format::Clustal), Ok(expected.clone()));
+        assert_eq!(
+            Msa::parse(stockholm, Format::Stockholm),
+            Ok(expected.clone())
+        );
+        assert_eq!(Msa::parse(phylip, Format::Phylip), Ok(expected));
+        assert!(Msa::<char>::parse("2 7\na ACG\nb A-G\nT-A\nTTA\n", Format::Phylip).is_err())
+    }
+
+    #[test]
+    fn gap_statistics() {
+        let msa = msa();
+        assert_eq!(msa.width(), 6);
+        assert_eq!(msa.column(2), vec![Some(DnaBase::G), None, None]);
+        assert_eq!(msa.column_gaps(), vec![0, 1, 2, 0, 1, 0]);
+        assert_eq!(msa.row_gaps(), vec![1, 1, 2]);
+        assert_eq!(msa.gapless_columns(), 3);
+        assert!((msa.gap_fraction() - 4.0 / 18.0).abs() < 1e-9);
+        assert!(Msa::<DnaBase>::parse(">a\nAC\n>b\nA", Format::Fasta).is_err())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::utils::digraph::Graph;
+use crate::utils::dna::{Dna, DnaBase};
+use crate::utils::fasta::Fasta;
+use crate::utils::sequence::Sequence;
+use std::collections::HashMap;
+
+/// A FASTA record borrowed as the payload of an overlap graph node
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Record<'a> {
+    name: &'a str,
+    sequence: &'a Dna,
+}
+
+impl<'a> Record<'a> {
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn sequence(&self) -> &'a Dna {
+        self.sequence
+    }
+}
+
+/// The records starting with each prefix
+type PrefixIndex<'a> = HashMap<&'a [DnaBase], Vec<usize>>;
+
+/// The records of `fasta` as graph nodes, with an index from each prefix of
+/// length `k` to the records starting with it. Records shorter than `k` are
+/// left out of the index.
+fn nodes_and_prefixes<'a>(
+    fasta: &'a Fasta<Dna>,
+    k: usize,
+) -> (Graph<Record<'a>, usize>, PrefixIndex<'a>) {
+    let mut graph = Graph::directed();
+    let mut prefixes = PrefixIndex::new();
+    for (name, sequence) in fasta.iter() {
+        let node = graph.add_node(Record { name, sequence });
+        if sequence.len() >= k {
+            prefixes
+                .entry(&sequence.symbols()[..k])
+                .or_default()
+                .push(node);
+        }
+    }
+    (graph, prefixes)
+}
+
+fn suffix(sequence: &Dna, k: usize) -> &[DnaBase] {
+    &sequence.symbols()[sequence.len() - k..]
+}
+
+impl<'a> Graph<Record<'a>, usize> {
+    /// The directed graph joining each record to every other record whose
+    /// prefix of length `k` matches its suffix, with edges weighted by `k`.
+    /// Prefixes are looked up in a hash index, so the time taken grows with
+    /// the number of records and edges rather than the number of pairs.
+    /// `k` must be at least 1.
+    pub fn overlap_graph(fasta: &'a Fasta<Dna>, k: usize) -> Self {
+        assert!(k > 0, "The overlap must be at least 1");
+        let (mut graph, prefixes) = nodes_and_prefixes(fasta, k);
+        for from in 0..graph.node_count() {
+            let sequence = graph.node(from).sequence;
+            if sequence.len() < k {
+                continue;
+            }
+            for &to in prefixes.get(suffix(sequence, k)).into_iter().flatten() {
+                if to != from {
+                    graph.add_edge(from, to, k);
+                }
+            }
+        }
+        graph
+    }
+
+    /// The directed graph joining each record to every other record that
+    /// overlaps its end by at least `minimum` symbols, with each edge
+    /// weighted by the length of the longest such overlap. `minimum` must be
+    /// at least 1.
+    pub fn minimum_overlap_graph(fasta: &'a Fasta<Dna>, minimum: usize) -> Self {
+        assert!(minimum > 0, "The minimum overlap must be at least 1");
+        let (mut graph, prefixes) = nodes_and_prefixes(fasta, minimum);
+        // A record whose prefix of length `minimum` occurs at position `i`
+        // of another is a candidate for an overlap of length `len - i`
+        for from in 0..graph.node_count() {
+            let symbols = graph.node(from).sequence.symbols();
+            if symbols.len() < minimum {
+                continue;
+            }
+            let mut overlaps: HashMap<usize, usize> = HashMap::new();
+            for start in 1..=symbols.len() - minimum {
+                for &to in prefixes
+                    .get(&symbols[start..start + minimum])
+                    .into_iter()
+                    .flatten()
+                {
+                    let length = symbols.len() - start;
+                    let other = graph.node(to).sequence.symbols();
+                    if to != from
+                        && !overlaps.contains_key(&to)
+                        && other.len() >= length
+                        && symbols[start..] == other[..length]
+                    {
+                        overlaps.insert(to, length);
+                    }
+                }
+            }
+            let mut overlaps: Vec<(usize, usize)> = overlaps.into_iter().collect();
+            overlaps.sort_unstable();
+            for (to, length) in overlaps {
+                graph.add_edge(from, to, length);
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn edges<'a>(graph: &Graph<Record<'a>, usize>) -> Vec<(&'a str, &'a str, usize)> {
+        graph
+            .edges()
+            .iter()
+            .map(|edge| {
+                (
+                    graph.node(edge.from).name(),
+                    graph.node(edge.to).name(),
+                    edge.weight,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exact_and_minimum_overlaps() {
+        let fasta =
+            Fasta::<Dna>::try_from(">a\nATTAGAC\n>b\nGACCTA\n>c\nAGACTT\n>d\nGA\n>e\nCTAGG")
+                .unwrap();
+        assert_eq!(
+            edges(&Graph::overlap_graph(&fasta, 3)),
+            vec![("a", "b", 3), ("b", "e", 3)]
+        );
+        // Short records are left out rather than panicking
+        assert_eq!(Graph::overlap_graph(&fasta, 6).edge_count(), 0);
+        assert_eq!(
+            edges(&Graph::minimum_overlap_graph(&fasta, 2)),
+            vec![("a", "b", 3), ("a", "c", 4), ("b", "e", 3)]
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "The minimum overlap must be at least 1")]
+    fn empty_overlaps_are_rejected() {
+        let fasta = Fasta::<Dna>::try_from(">a\nACGT\n>b\nTTTT").unwrap();
+        Graph::minimum_overlap_graph(&fasta, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "The overlap must be at least 1")]
+    fn empty_exact_overlaps_are_rejected() {
+        let fasta = Fasta::<Dna>::try_from(">a\nACGT\n>b\nTTTT").unwrap();
+        Graph::overlap_graph(&fasta, 0);
+    }
+}
@@ -0,0 +1,384 @@
+use crate::utils::dna::DnaBase;
+use crate::utils::fasta::Fasta;
+use crate::utils::msa::Msa;
+use crate::utils::sequence::{Alphabet, Sequence};
+use std::marker::PhantomData;
+
+/// Symbol counts for each column of a set of equal length sequences, with an
+/// optional pseudocount added to every count when computing frequencies.
+/// Only the symbols in `Alphabet::RESIDUES` are counted, so stop codons are
+/// left out like gaps.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Profile<T> {
+    /// Counts indexed by `column * T::RESIDUES.len() + symbol.index()`
+    counts: Vec<usize>,
+    columns: usize,
+    /// The number of sequences counted, including any gaps
+    rows: usize,
+    pseudocount: f64,
+    symbols: PhantomData<T>,
+}
+
+impl<T: Alphabet> Profile<T> {
+    /// Count the symbols of sequences which must all be the same length
+    pub fn from_sequences(sequences: &[&[T]]) -> Result<Self, String> {
+        let columns = sequences.first().map_or(0, |sequence| sequence.len());
+        let mut profile = Self::empty(columns, sequences.len());
+        for (i, sequence) in sequences.iter().enumerate() {
+            if sequence.len() != columns {
+                return Err(format!(
+                    "Sequence {} has length {}, expected {}",
+                    i + 1,
+                    sequence.len(),
+                    columns
+                ));
+            }
+            for (column, &symbol) in sequence.iter().enumerate() {
+                profile.add(column, symbol);
+            }
+        }
+        Ok(profile)
+    }
+
+    pub fn from_fasta<S>(fasta: &Fasta<S>) -> Result<Self, String>
+    where
+        S: Sequence<Symbol = T>,
+    {
+        let sequences: Vec<&[T]> = fasta.iter().map(|(_, s)| s.symbols()).collect();
+        Self::from_sequences(&sequences)
+    }
+
+    /// Count the symbols in each column of an alignment, ignoring gaps
+    pub fn from_msa(msa: &Msa<T>) -> Self {
+        let mut profile = Self::empty(msa.width(), msa.row_count());
+        for (column, symbols) in msa.columns().enumerate() {
+            for symbol in symbols.into_iter().flatten() {
+                profile.add(column, symbol);
+            }
+        }
+        profile
+    }
+
+    fn empty(columns: usize, rows: usize) -> Self {
+        Profile {
+            counts: vec![0; columns * T::RESIDUES.len()],
+            columns,
+            rows,
+            pseudocount: 0.0,
+            symbols: PhantomData,
+        }
+    }
+
+    fn add(&mut self, column: usize, symbol: T) {
+        if symbol.index() < T::RESIDUES.len() {
+            self.counts[column * T::RESIDUES.len() + symbol.index()] += 1;
+        }
+    }
+
+    /// Add `pseudocount` to every count when computing frequencies, so that
+    /// unseen symbols are not given a probability of zero
+    pub fn with_pseudocount(mut self, pseudocount: f64) -> Self {
+        self.pseudocount = pseudocount;
+        self
+    }
+
+    /// The number of columns
+    pub fn len(&self) -> usize {
+        self.columns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns == 0
+    }
+
+    pub fn count(&self, column: usize, symbol: T) -> usize {
+        if symbol.index() < T::RESIDUES.len() {
+            self.counts[column * T::RESIDUES.len() + symbol.index()]
+        } else {
+            0
+        }
+    }
+
+    /// The counts of `symbol` in each column
+    pub fn symbol_counts(&self, symbol: T) -> Vec<usize> {
+        (0..self.columns)
+            .map(|column| self.count(column, symbol))
+            .collect()
+    }
+
+    fn column_counts(&self, column: usize) -> &[usize] {
+        let size = T::RESIDUES.len();
+        &self.counts[column * size..(column + 1) * size]
+    }
+
+    /// The fraction of rows with a counted symbol in each column, which is
+    /// lowered by gaps and stop codons
+    pub fn occupancy(&self) -> Vec<f64> {
+        (0..self.columns)
+            .map(|column| {
+                if self.rows == 0 {
+                    0.0
+                } else {
+                    self.column_counts(column).iter().sum::<usize>() as f64 / self.rows as f64
+                }
+            })
+            .collect()
+    }
+
+    /// The frequency of each symbol in each column, including pseudocounts.
+    /// Columns without any symbols or pseudocounts are uniform.
+    pub fn frequencies(&self) -> Vec<Vec<f64>> {
+        let size = T::RESIDUES.len();
+        (0..self.columns)
+            .map(|column| {
+                let counts = self.column_counts(column);
+                let total = counts.iter().sum::<usize>() as f64 + self.pseudocount * size as f64;
+                counts
+                    .iter()
+                    .map(|&count| {
+                        if total > 0.0 {
+                            (count as f64 + self.pseudocount) / total
+                        } else {
+                            1.0 / size as f64
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The Shannon entropy of each column in bits
+    pub fn entropy(&self) -> Vec<f64> {
+        self.frequencies()
+            .iter()
+            .map(|column| {
+                -column
+                    .iter()
+                    .filter(|&&p| p > 0.0)
+                    .map(|&p| p * p.log2())
+                    .sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// The information content of each column in bits: how far its entropy
+    /// falls below that of a uniform column
+    pub fn information_content(&self) -> Vec<f64> {
+        let maximum = (T::RESIDUES.len() as f64).log2();
+        self.entropy()
+            .iter()
+            .map(|entropy| maximum - entropy)
+            .collect()
+    }
+
+    /// The most common symbol in each column, taking the earliest in the
+    /// alphabet on ties, or `None` for columns without any symbols
+    pub fn consensus(&self) -> Vec<Option<T>> {
+        (0..self.columns)
+            .map(|column| {
+                let counts = self.column_counts(column);
+                let best = counts.iter().copied().max().filter(|&best| best > 0)?;
+                Some(T::RESIDUES[counts.iter().position(|&count| count == best)?])
+            })
+            .collect()
+    }
+
+    /// Log-odds scores of each symbol in each column against `background`
+    /// symbol frequencies, in bits. A uniform background is used if
+    /// `background` is `None`.
+    pub fn log_odds(&self, background: Option<&[f64]>) -> Pwm<T> {
+        let size = T::RESIDUES.len();
+        let uniform = vec![1.0 / size as f64; size];
+        let background = background.unwrap_or(&uniform);
+        let scores = self
+            .frequencies()
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .zip(background)
+                    .map(|(&p, &q)| (p / q).log2())
+                    .collect()
+            })
+            .collect();
+        Pwm {
+            scores,
+            symbols: PhantomData,
+        }
+    }
+}
+
+/// IUPAC codes for sets of bases, indexed by a bit set of A, C, G and T
+const IUPAC: [char; 16] = [
+    '-', 'A', 'C', 'M', 'G', 'R', 'S', 'V', 'T', 'W', 'Y', 'H', 'K', 'D', 'B', 'N',
+];
+
+impl Profile<DnaBase> {
+    /// A consensus using IUPAC ambiguity codes: each column is given the code
+    /// for the most frequent bases making up at least `threshold` of its
+    /// frequency. The most frequent base is always included, and so is every
+    /// base as frequent as the last one included, so ties are never split.
+    /// Columns without any bases are written as `-`.
+    pub fn iupac_consensus(&self, threshold: f64) -> String {
+        (0..self.columns)
+            .map(|column| {
+                let counts = self.column_counts(column);
+                let total: usize = counts.iter().sum();
+                if total == 0 {
+                    return '-';
+                }
+                let mut bases: Vec<usize> = (0..counts.len()).collect();
+                bases.sort_by_key(|&base| std::cmp::Reverse(counts[base]));
+                let (mut covered, mut set) = (0, 0);
+                let mut last = None;
+                for base in bases {
+                    if last.map_or(false, |last| last != counts[base])
+                        && covered as f64 >= threshold * total as f64
+                    {
+                        break;
+                    }
+                    covered += counts[base];
+                    set |= 1 << base;
+                    last = Some(counts[base]);
+                }
+                IUPAC[set]
+            })
+            .collect()
+    }
+}
+
+/// A position weight matrix, scoring each symbol at each position of a motif
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pwm<T> {
+    scores: Vec<Vec<f64>>,
+    symbols: PhantomData<T>,
+}
+
+impl<T: Alphabet> Pwm<T> {
+    /// The length of the motif
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn scores(&self) -> &[Vec<f64>] {
+        &self.scores
+    }
+
+    /// The score of `window`, which must be the length of the motif. Symbols
+    /// that profiles leave out, such as stop codons, score minus infinity.
+    pub fn score(&self, window: &[T]) -> f64 {
+        self.scores
+            .iter()
+            .zip(window)
+            .map(|(column, symbol)| {
+                column
+                    .get(symbol.index())
+                    .copied()
+                    .unwrap_or(f64::NEG_INFINITY)
+            })
+            .sum()
+    }
+
+    /// The score of the motif at every position of `sequence`
+    pub fn scan(&self, sequence: &[T]) -> Vec<f64> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        sequence
+            .windows(self.len())
+            .map(|window| self.score(window))
+            .collect()
+    }
+
+    /// The positions of `sequence` where the motif scores at least `threshold`
+    pub fn hits(&self, sequence: &[T], threshold: f64) -> Vec<(usize, f64)> {
+        self.scan(sequence)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, score)| score >= threshold)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::Dna;
+    use crate::utils::msa::Format;
+    use std::convert::TryFrom;
+
+    fn profile() -> Profile<DnaBase> {
+        Profile::from_fasta(
+            &Fasta::<Dna>::try_from(">a\nACGA\n>b\nACGT\n>c\nAGCT\n>d\nATCT").unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn counts_and_frequencies() {
+        let profile = profile();
+        assert_eq!(profile.symbol_counts(DnaBase::A), vec![4, 0, 0, 1]);
+        assert_eq!(profile.symbol_counts(DnaBase::C), vec![0, 2, 2, 0]);
+        assert_eq!(profile.frequencies()[1], vec![0.0, 0.5, 0.25, 0.25]);
+        let smoothed = profile.clone().with_pseudocount(1.0).frequencies();
+        assert_eq!(
+            smoothed[0],
+            vec![5.0 / 8.0, 1.0 / 8.0, 1.0 / 8.0, 1.0 / 8.0]
+        );
+        assert_eq!(
+            Profile::<DnaBase>::from_fasta(&Fasta::<Dna>::try_from(">a\nAC\n>b\nA").unwrap()),
+            Err("Sequence 2 has length 1, expected 2".to_string())
+        );
+        let msa = Msa::parse(">a\nA-G\n>b\nACG", Format::Fasta).unwrap();
+        assert_eq!(
+            Profile::from_msa(&msa).symbol_counts(DnaBase::C),
+            vec![0, 1, 0]
+        )
+    }
+
+    #[test]
+    fn information_and_consensus() {
+        let profile = profile();
+        assert_eq!(profile.entropy(), vec![0.0, 1.5, 1.0, 0.8112781244591328]);
+        assert_eq!(profile.information_content()[0], 2.0);
+        // The third column ties, and takes the base earliest in the alphabet
+        assert_eq!(
+            profile.consensus(),
+            vec![
+                Some(DnaBase::A),
+                Some(DnaBase::C),
+                Some(DnaBase::C),
+                Some(DnaBase::T)
+            ]
+        );
+        let msa = Msa::parse(">a\nA-G\n>b\nC-G", Format::Fasta).unwrap();
+        assert_eq!(
+            Profile::from_msa(&msa).consensus(),
+            vec![Some(DnaBase::A), None, Some(DnaBase::G)]
+        );
+        let msa: Msa<DnaBase> =
+            Msa::parse(">a\nA-G\n>b\nC-G\n>c\nA--\n>d\n--T", Format::Fasta).unwrap();
+        assert_eq!(Profile::from_msa(&msa).occupancy(), vec![0.75, 0.0, 0.75]);
+        assert_eq!(profile.occupancy(), vec![1.0; 4]);
+        assert_eq!(profile.iupac_consensus(0.9), "ABSW");
+        // Tied bases are included together, and the top base at any threshold
+        assert_eq!(profile.iupac_consensus(0.5), "ACST");
+        assert_eq!(profile.iupac_consensus(0.6), "ABST");
+        assert_eq!(profile.iupac_consensus(0.0), "ACST")
+    }
+
+    #[test]
+    fn pwm_scanning() {
+        let pwm = profile().with_pseudocount(0.5).log_odds(None);
+        let sequence = Dna::try_from("TTACGTTAGCTT").unwrap();
+        let scores = pwm.scan(sequence.symbols());
+        assert_eq!(scores.len(), 9);
+        let hits = pwm.hits(sequence.symbols(), 3.0);
+        assert_eq!(hits.iter().map(|&(i, _)| i).collect::<Vec<_>>(), vec![2, 7]);
+        assert_eq!(scores[2], pwm.score(&sequence.symbols()[2..6]))
+    }
+}
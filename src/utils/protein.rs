@@ -1,6 +1,6 @@
 use crate::utils::dna::{Dna, DnaBase};
 use crate::utils::rna::{Rna, RnaBase};
-use crate::utils::sequence::Sequence;
+use crate::utils::sequence::{Alphabet, Sequence};
 use std::convert::TryFrom;
 
 enum Match {
@@ -52,6 +52,10 @@ impl ProteinMotif {
         self.pattern.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.pattern.is_empty()
+    }
+
     /// Test whether this pattern matches the given slice
     fn matches(&self, sequence: &[AminoAcid]) -> bool {
         for (match_, aa) in self.pattern.iter().zip(sequence.iter()) {
@@ -77,8 +81,8 @@ impl ProteinMotif {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum AminoAcid {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AminoAcid {
     A,
     C,
     D,
@@ -102,6 +106,59 @@ enum AminoAcid {
     Stop,
 }
 
+impl Alphabet for AminoAcid {
+    const SYMBOLS: &'static [AminoAcid] = &[
+        AminoAcid::A,
+        AminoAcid::C,
+        AminoAcid::D,
+        AminoAcid::E,
+        AminoAcid::F,
+        AminoAcid::G,
+        AminoAcid::H,
+        AminoAcid::I,
+        AminoAcid::K,
+        AminoAcid::L,
+        AminoAcid::M,
+        AminoAcid::N,
+        AminoAcid::P,
+        AminoAcid::Q,
+        AminoAcid::R,
+        AminoAcid::S,
+        AminoAcid::T,
+        AminoAcid::V,
+        AminoAcid::W,
+        AminoAcid::Y,
+        AminoAcid::Stop,
+    ];
+
+    const RESIDUES: &'static [AminoAcid] = &[
+        AminoAcid::A,
+        AminoAcid::C,
+        AminoAcid::D,
+        AminoAcid::E,
+        AminoAcid::F,
+        AminoAcid::G,
+        AminoAcid::H,
+        AminoAcid::I,
+        AminoAcid::K,
+        AminoAcid::L,
+        AminoAcid::M,
+        AminoAcid::N,
+        AminoAcid::P,
+        AminoAcid::Q,
+        AminoAcid::R,
+        AminoAcid::S,
+        AminoAcid::T,
+        AminoAcid::V,
+        AminoAcid::W,
+        AminoAcid::Y,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
 impl TryFrom<char> for AminoAcid {
     type Error = String;
 
@@ -232,7 +289,7 @@ impl Protein {
     pub fn find_motif_locations(&self, motif: &ProteinMotif) -> Vec<usize> {
         let mut locations = Vec::new();
 
-        for (i, candidate) in (&self.sequence).windows(motif.len()).enumerate() {
+        for (i, candidate) in self.sequence.windows(motif.len()).enumerate() {
             if motif.matches(candidate) {
                 locations.push(i + 1)
             }
@@ -242,7 +299,17 @@ impl Protein {
     }
 }
 
-impl Sequence for Protein {}
+impl Sequence for Protein {
+    type Symbol = AminoAcid;
+
+    /// The amino acids of this protein, without the terminating stop
+    fn symbols(&self) -> &[AminoAcid] {
+        match self.sequence.split_last() {
+            Some((AminoAcid::Stop, rest)) => rest,
+            _ => &self.sequence,
+        }
+    }
+}
 
 impl std::fmt::Display for Protein {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -0,0 +1,78 @@
+/// A small seedable pseudo-random number generator (SplitMix64), so that
+/// randomized algorithms give reproducible results for a given seed. It is
+/// not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniformly distributed integer in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Cannot choose from an empty range");
+        // Rejection sampling avoids favouring small values
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+
+    /// An index chosen with probability proportional to its weight
+    pub fn weighted(&mut self, weights: &[f64]) -> usize {
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return self.below(weights.len());
+        }
+        let mut target = self.next_f64() * total;
+        for (i, &weight) in weights.iter().enumerate() {
+            if target < weight {
+                return i;
+            }
+            target -= weight;
+        }
+        // Rounding may leave a little of the total unaccounted for
+        weights.iter().rposition(|&weight| weight > 0.0).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let mut rng = Rng::new(7);
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            assert!(rng.below(5) < 5);
+            counts[rng.weighted(&[1.0, 0.0, 2.0])] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > counts[0] * 3 / 2)
+    }
+}
@@ -1,8 +1,8 @@
 use crate::utils::dna::{Dna, DnaBase};
-use crate::utils::sequence::Sequence;
+use crate::utils::sequence::{Alphabet, Sequence};
 use std::convert::{From, TryFrom};
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub enum RnaBase {
     A,
     C,
@@ -10,6 +10,28 @@ pub enum RnaBase {
     U,
 }
 
+impl Alphabet for RnaBase {
+    const SYMBOLS: &'static [RnaBase] = &[RnaBase::A, RnaBase::C, RnaBase::G, RnaBase::U];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl TryFrom<char> for RnaBase {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_lowercase() {
+            'a' => Ok(RnaBase::A),
+            'c' => Ok(RnaBase::C),
+            'g' => Ok(RnaBase::G),
+            'u' => Ok(RnaBase::U),
+            c => Err(format!("Failed to convert {} to rna base", c)),
+        }
+    }
+}
+
 impl std::fmt::Display for RnaBase {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -35,12 +57,22 @@ impl Rna {
         self.sequence.len()
     }
 
-    pub fn chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<RnaBase> {
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    pub fn chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<'_, RnaBase> {
         self.sequence.chunks_exact(chunk_size)
     }
 }
 
-impl Sequence for Rna {}
+impl Sequence for Rna {
+    type Symbol = RnaBase;
+
+    fn symbols(&self) -> &[RnaBase] {
+        &self.sequence
+    }
+}
 
 impl std::fmt::Display for Rna {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -104,16 +136,10 @@ impl TryFrom<String> for Rna {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let mut sequence = Vec::with_capacity(value.len());
-        for ch in value.chars() {
-            match ch.to_ascii_lowercase() {
-                'a' => sequence.push(RnaBase::A),
-                'c' => sequence.push(RnaBase::C),
-                'g' => sequence.push(RnaBase::G),
-                'u' => sequence.push(RnaBase::U),
-                c => return Err(format!("Failed to convert {} to rna base", c)),
-            }
-        }
+        let sequence = value
+            .chars()
+            .map(RnaBase::try_from)
+            .collect::<Result<_, _>>()?;
         Ok(Rna { sequence })
     }
 }
@@ -122,16 +148,10 @@ impl TryFrom<&str> for Rna {
     type Error = String;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut sequence = Vec::with_capacity(value.len());
-        for ch in value.chars() {
-            match ch.to_ascii_lowercase() {
-                'a' => sequence.push(RnaBase::A),
-                'c' => sequence.push(RnaBase::C),
-                'g' => sequence.push(RnaBase::G),
-                'u' => sequence.push(RnaBase::U),
-                c => return Err(format!("Failed to convert {} to rna base", c)),
-            }
-        }
+        let sequence = value
+            .chars()
+            .map(RnaBase::try_from)
+            .collect::<Result<_, _>>()?;
         Ok(Rna { sequence })
     }
 }
@@ -0,0 +1,72 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::strongly_connected_components;
+
+/// The implication graph node for a literal: `2v` for variable `v` (from 0)
+/// and `2v + 1` for its negation
+fn literal_node(literal: i64) -> usize {
+    let variable = (literal.unsigned_abs() - 1) as usize;
+    2 * variable + (literal < 0) as usize
+}
+
+/// Solve a 2-SAT formula over variables numbered from 1, given as clauses
+/// of two literals where `-v` is the negation of variable `v`. Returns a
+/// satisfying assignment of each variable if there is one.
+pub fn two_sat(variables: usize, clauses: &[(i64, i64)]) -> Result<Option<Vec<bool>>, String> {
+    if let Some(&(a, b)) = clauses.iter().find(|&&(a, b)| {
+        [a, b]
+            .iter()
+            .any(|&literal| literal == 0 || literal.unsigned_abs() as usize > variables)
+    }) {
+        return Err(format!("Clause {} {} refers to a missing variable", a, b));
+    }
+
+    // Each clause a or b gives the implications not a => b and not b => a
+    let mut graph = Graph::directed();
+    for _ in 0..2 * variables {
+        graph.add_node(());
+    }
+    for &(a, b) in clauses {
+        graph.add_edge(literal_node(-a), literal_node(b), ());
+        graph.add_edge(literal_node(-b), literal_node(a), ());
+    }
+
+    let mut component_of = vec![0; graph.node_count()];
+    for (i, component) in strongly_connected_components(&graph).iter().enumerate() {
+        for &node in component {
+            component_of[node] = i;
+        }
+    }
+    // A literal is made true when nothing it implies comes after its negation
+    Ok((0..variables)
+        .map(|variable| {
+            let (positive, negative) = (component_of[2 * variable], component_of[2 * variable + 1]);
+            if positive == negative {
+                None
+            } else {
+                Some(positive > negative)
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(assignment: &[bool], clauses: &[(i64, i64)]) -> bool {
+        let value = |literal: i64| assignment[literal.unsigned_abs() as usize - 1] == (literal > 0);
+        clauses.iter().all(|&(a, b)| value(a) || value(b))
+    }
+
+    #[test]
+    fn solves_and_refutes() {
+        let clauses = [(1, 2), (-1, 3), (-2, -3), (2, 3), (-1, -2)];
+        let assignment = two_sat(3, &clauses).unwrap().unwrap();
+        assert!(satisfies(&assignment, &clauses));
+        assert_eq!(two_sat(1, &[(1, 1), (-1, -1)]), Ok(None));
+        assert_eq!(
+            two_sat(2, &[(1, 3)]),
+            Err("Clause 1 3 refers to a missing variable".to_string())
+        )
+    }
+}
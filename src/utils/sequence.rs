@@ -1,3 +1,23 @@
 use std::convert::TryFrom;
+use std::fmt::Display;
 
-pub trait Sequence: TryFrom<String> {}
+/// A symbol drawn from a fixed, ordered alphabet
+pub trait Alphabet: Copy + Ord + Display + 'static {
+    /// Every symbol of the alphabet in lexicographic order
+    const SYMBOLS: &'static [Self];
+
+    /// The symbols counted in profiles and sequence logos, leaving out
+    /// markers such as the stop codon. These come first in `SYMBOLS`, so
+    /// `index` is also a position in this list.
+    const RESIDUES: &'static [Self] = Self::SYMBOLS;
+
+    /// The position of this symbol in `SYMBOLS`
+    fn index(self) -> usize;
+}
+
+pub trait Sequence: TryFrom<String> {
+    type Symbol: Alphabet;
+
+    /// The symbols making up this sequence
+    fn symbols(&self) -> &[Self::Symbol];
+}
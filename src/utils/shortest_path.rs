@@ -0,0 +1,244 @@
+use crate::utils::digraph::Graph;
+use crate::utils::traversal::topological_sort;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// The lengths of the best paths from a source to every node, with the node
+/// before each one on its path so that the paths can be rebuilt
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ShortestPaths {
+    source: usize,
+    distances: Vec<Option<i64>>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    fn new(node_count: usize, source: usize) -> Self {
+        let mut paths = ShortestPaths {
+            source,
+            distances: vec![None; node_count],
+            predecessors: vec![None; node_count],
+        };
+        paths.distances[source] = Some(0);
+        paths
+    }
+
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// The length of the path to `node`, or `None` if it cannot be reached
+    pub fn distance(&self, node: usize) -> Option<i64> {
+        self.distances[node]
+    }
+
+    pub fn distances(&self) -> &[Option<i64>] {
+        &self.distances
+    }
+
+    /// The node before `node` on its path, which is `None` for the source
+    /// and for nodes that cannot be reached
+    pub fn predecessor(&self, node: usize) -> Option<usize> {
+        self.predecessors[node]
+    }
+
+    /// The nodes on the path from the source to `node`, if it can be reached
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        self.distances[node]?;
+        let mut path = vec![node];
+        while let Some(previous) = self.predecessors[*path.last().unwrap()] {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Dijkstra's algorithm using a binary heap. Every edge weight must be
+/// non-negative.
+pub fn dijkstra<N>(graph: &Graph<N, i64>, source: usize) -> Result<ShortestPaths, String> {
+    if let Some(edge) = graph.edges().iter().find(|edge| edge.weight < 0) {
+        return Err(format!(
+            "Edge {} -> {} has negative weight {}",
+            edge.from + 1,
+            edge.to + 1,
+            edge.weight
+        ));
+    }
+    let mut paths = ShortestPaths::new(graph.node_count(), source);
+    let mut done = vec![false; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0, source)));
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        if done[node] {
+            continue;
+        }
+        done[node] = true;
+        for (other, &weight) in graph.neighbours(node) {
+            let candidate = distance + weight;
+            if paths.distances[other].map_or(true, |current| candidate < current) {
+                paths.distances[other] = Some(candidate);
+                paths.predecessors[other] = Some(node);
+                heap.push(Reverse((candidate, other)));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Relax every edge up to once per node. Returns a node relaxed in the last
+/// round, which only happens when a negative cycle leads to it.
+fn relax_all<N>(graph: &Graph<N, i64>, paths: &mut ShortestPaths) -> Option<usize> {
+    let mut changed = None;
+    for _ in 0..graph.node_count() {
+        changed = None;
+        for node in 0..graph.node_count() {
+            let distance = match paths.distances[node] {
+                Some(distance) => distance,
+                None => continue,
+            };
+            for (other, &weight) in graph.neighbours(node) {
+                let candidate = distance + weight;
+                if paths.distances[other].map_or(true, |current| candidate < current) {
+                    paths.distances[other] = Some(candidate);
+                    paths.predecessors[other] = Some(node);
+                    changed = Some(other);
+                }
+            }
+        }
+        changed?;
+    }
+    changed
+}
+
+/// Follow predecessors back from a node that a negative cycle leads to,
+/// returning the cycle in path order
+fn cycle_before(paths: &ShortestPaths, mut node: usize) -> Vec<usize> {
+    // After stepping back once per node we must be on the cycle
+    for _ in 0..paths.predecessors.len() {
+        node = paths.predecessors[node].unwrap();
+    }
+    let mut cycle = vec![node];
+    let mut previous = paths.predecessors[node].unwrap();
+    while previous != node {
+        cycle.push(previous);
+        previous = paths.predecessors[previous].unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+/// The Bellman-Ford algorithm, which allows negative edge weights. If a
+/// negative weight cycle can be reached from `source`, so that some paths
+/// have no shortest length, the nodes of one such cycle are returned instead.
+pub fn bellman_ford<N>(graph: &Graph<N, i64>, source: usize) -> Result<ShortestPaths, Vec<usize>> {
+    let mut paths = ShortestPaths::new(graph.node_count(), source);
+    match relax_all(graph, &mut paths) {
+        Some(node) => Err(cycle_before(&paths, node)),
+        None => Ok(paths),
+    }
+}
+
+/// The nodes of a negative weight cycle anywhere in the graph, if there is one
+pub fn negative_cycle<N>(graph: &Graph<N, i64>) -> Option<Vec<usize>> {
+    if graph.node_count() == 0 {
+        return None;
+    }
+    // Starting every node at distance 0 is the same as searching from an
+    // extra node with an edge of weight 0 to each of them
+    let mut paths = ShortestPaths::new(graph.node_count(), 0);
+    paths.distances = vec![Some(0); graph.node_count()];
+    relax_all(graph, &mut paths).map(|node| cycle_before(&paths, node))
+}
+
+fn dag_paths<N>(
+    graph: &Graph<N, i64>,
+    source: usize,
+    better: impl Fn(i64, i64) -> bool,
+) -> Result<ShortestPaths, Vec<usize>> {
+    let order = topological_sort(graph)?;
+    let mut paths = ShortestPaths::new(graph.node_count(), source);
+    for node in order {
+        let distance = match paths.distances[node] {
+            Some(distance) => distance,
+            None => continue,
+        };
+        for (other, &weight) in graph.neighbours(node) {
+            let candidate = distance + weight;
+            if paths.distances[other].map_or(true, |current| better(candidate, current)) {
+                paths.distances[other] = Some(candidate);
+                paths.predecessors[other] = Some(node);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Shortest paths in a directed acyclic graph, visiting the nodes in
+/// topological order. Edge weights may be negative. If the graph has a
+/// cycle, its nodes are returned instead.
+pub fn dag_shortest_paths<N>(
+    graph: &Graph<N, i64>,
+    source: usize,
+) -> Result<ShortestPaths, Vec<usize>> {
+    dag_paths(graph, source, |candidate, current| candidate < current)
+}
+
+/// Longest paths in a directed acyclic graph, as for `dag_shortest_paths`
+pub fn dag_longest_paths<N>(
+    graph: &Graph<N, i64>,
+    source: usize,
+) -> Result<ShortestPaths, Vec<usize>> {
+    dag_paths(graph, source, |candidate, current| candidate > current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_and_predecessors() {
+        let graph =
+            Graph::from_edge_list("5 6\n1 2 4\n1 3 1\n3 2 2\n2 4 1\n3 4 5\n4 1 3", true).unwrap();
+        let paths = dijkstra(&graph, 0).unwrap();
+        assert_eq!(
+            paths.distances(),
+            &[Some(0), Some(3), Some(1), Some(4), None]
+        );
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.path_to(4), None);
+        assert_eq!(bellman_ford(&graph, 0), Ok(paths));
+
+        let negative = Graph::from_edge_list("2 1\n1 2 -1", true).unwrap();
+        assert_eq!(
+            dijkstra(&negative, 0),
+            Err("Edge 1 -> 2 has negative weight -1".to_string())
+        )
+    }
+
+    #[test]
+    fn negative_cycles() {
+        let graph = Graph::from_edge_list("4 4\n1 2 1\n2 3 -2\n3 2 1\n4 1 1", true).unwrap();
+        assert_eq!(bellman_ford(&graph, 0), Err(vec![2, 1]));
+        assert_eq!(negative_cycle(&graph), Some(vec![2, 1]));
+        assert!(bellman_ford(&graph, 3).is_err());
+        let unreachable = Graph::from_edge_list("3 3\n2 3 -2\n3 2 1\n1 1 0", true).unwrap();
+        assert!(bellman_ford(&unreachable, 0).is_ok());
+        assert!(negative_cycle(&unreachable).is_some())
+    }
+
+    #[test]
+    fn dag_paths_in_both_directions() {
+        let graph = Graph::from_edge_list("4 4\n1 2 1\n1 3 2\n2 4 5\n3 4 1", true).unwrap();
+        let shortest = dag_shortest_paths(&graph, 0).unwrap();
+        let longest = dag_longest_paths(&graph, 0).unwrap();
+        assert_eq!(shortest.path_to(3), Some(vec![0, 2, 3]));
+        assert_eq!(longest.path_to(3), Some(vec![0, 1, 3]));
+        assert_eq!(
+            (shortest.distance(3), longest.distance(3)),
+            (Some(3), Some(6))
+        );
+        let cyclic = Graph::from_edge_list("2 2\n1 2 1\n2 1 1", true).unwrap();
+        assert_eq!(dag_longest_paths(&cyclic, 0), Err(vec![0, 1]))
+    }
+}
@@ -0,0 +1,185 @@
+use std::cmp::Reverse;
+
+/// The lengths of the longest common subsequences of every pair of prefixes
+fn prefix_lengths<T: Eq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
+    let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            lengths[i + 1][j + 1] = if x == y {
+                lengths[i][j] + 1
+            } else {
+                lengths[i][j + 1].max(lengths[i + 1][j])
+            };
+        }
+    }
+    lengths
+}
+
+/// A longest sequence which is a subsequence of both `a` and `b`: its
+/// symbols appear in order in each, though not necessarily together
+pub fn longest_common_subsequence<T: Eq + Copy>(a: &[T], b: &[T]) -> Vec<T> {
+    let lengths = prefix_lengths(a, b);
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut common = Vec::with_capacity(lengths[i][j]);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            common.push(a[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if lengths[i - 1][j] >= lengths[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    common.reverse();
+    common
+}
+
+/// A shortest sequence having both `a` and `b` as subsequences, made by
+/// merging them around a longest common subsequence
+pub fn shortest_common_supersequence<T: Eq + Copy>(a: &[T], b: &[T]) -> Vec<T> {
+    let lengths = prefix_lengths(a, b);
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut merged = Vec::with_capacity(a.len() + b.len() - lengths[i][j]);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] {
+            merged.push(a[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if j == 0 || (i > 0 && lengths[i - 1][j] >= lengths[i][j - 1]) {
+            merged.push(a[i - 1]);
+            i -= 1;
+        } else {
+            merged.push(b[j - 1]);
+            j -= 1;
+        }
+    }
+    merged.reverse();
+    merged
+}
+
+/// The positions in `sequence` of the earliest occurrence of `motif` as a
+/// subsequence, or `None` if it does not occur
+pub fn subsequence_indices<T: Eq>(sequence: &[T], motif: &[T]) -> Option<Vec<usize>> {
+    let mut indices = Vec::with_capacity(motif.len());
+    let mut positions = sequence.iter().enumerate();
+    for symbol in motif {
+        let (i, _) = positions.find(|(_, s)| *s == symbol)?;
+        indices.push(i);
+    }
+    Some(indices)
+}
+
+/// The number of ways of choosing positions in `sequence` spelling out
+/// `motif`, modulo `modulus`, which must be positive
+pub fn count_subsequences<T: Eq>(sequence: &[T], motif: &[T], modulus: u64) -> u64 {
+    assert!(modulus > 0, "The modulus must be positive");
+    // Ways of spelling each prefix of the motif so far
+    let mut ways = vec![0u64; motif.len() + 1];
+    ways[0] = 1 % modulus;
+    for symbol in sequence {
+        for j in (0..motif.len()).rev() {
+            if motif[j] == *symbol {
+                ways[j + 1] = ((ways[j + 1] as u128 + ways[j] as u128) % modulus as u128) as u64;
+            }
+        }
+    }
+    ways[motif.len()]
+}
+
+/// A longest strictly increasing subsequence, found by patience sorting in
+/// `O(n log n)` time
+pub fn longest_increasing_subsequence<T: Ord + Copy>(sequence: &[T]) -> Vec<T> {
+    // The index of the smallest last element of an increasing subsequence of
+    // each length, and the element before each element in its subsequence
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; sequence.len()];
+    for (i, x) in sequence.iter().enumerate() {
+        let length = tails.partition_point(|&tail| sequence[tail] < *x);
+        if length > 0 {
+            previous[i] = Some(tails[length - 1]);
+        }
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        subsequence.push(sequence[i]);
+        current = previous[i];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+/// A longest strictly decreasing subsequence
+pub fn longest_decreasing_subsequence<T: Ord + Copy>(sequence: &[T]) -> Vec<T> {
+    let reversed: Vec<Reverse<T>> = sequence.iter().map(|&x| Reverse(x)).collect();
+    longest_increasing_subsequence(&reversed)
+        .into_iter()
+        .map(|Reverse(x)| x)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_subsequence(sequence: &[u8], motif: &[u8]) -> bool {
+        subsequence_indices(sequence, motif).is_some()
+    }
+
+    #[test]
+    fn common_subsequences_and_supersequences() {
+        for (a, b, common) in &[
+            (&b"AACCTTGG"[..], &b"ACACTGTGA"[..], 6),
+            (b"ATCTGAT", b"TGCATA", 4),
+            (b"", b"ACGT", 0),
+            (b"AAAA", b"CCCC", 0),
+        ] {
+            let lcs = longest_common_subsequence(a, b);
+            assert_eq!(lcs.len(), *common);
+            assert!(is_subsequence(a, &lcs) && is_subsequence(b, &lcs));
+            let scs = shortest_common_supersequence(a, b);
+            assert_eq!(scs.len(), a.len() + b.len() - common);
+            assert!(is_subsequence(&scs, a) && is_subsequence(&scs, b));
+        }
+    }
+
+    #[test]
+    fn counts_subsequence_occurrences() {
+        assert_eq!(count_subsequences(b"rabbbit", b"rabbit", u64::MAX), 3);
+        assert_eq!(count_subsequences(b"AAAA", b"AA", u64::MAX), 6);
+        assert_eq!(count_subsequences(b"AAAA", b"AA", 4), 2);
+        assert_eq!(count_subsequences(b"ACGT", b"", u64::MAX), 1);
+        assert_eq!(subsequence_indices(b"ACGT", b"TA"), None)
+    }
+
+    #[test]
+    #[should_panic(expected = "The modulus must be positive")]
+    fn zero_modulus_is_rejected() {
+        count_subsequences(b"AAAA", b"AA", 0);
+    }
+
+    #[test]
+    fn monotonic_subsequences() {
+        assert_eq!(
+            longest_increasing_subsequence(&[5, 1, 4, 2, 3]),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            longest_decreasing_subsequence(&[5, 1, 4, 2, 3]),
+            vec![5, 4, 3]
+        );
+        assert_eq!(
+            longest_increasing_subsequence(&[3, 3, 1, 2, 2, 5]),
+            vec![1, 2, 5]
+        );
+        assert!(longest_increasing_subsequence::<u32>(&[]).is_empty())
+    }
+}
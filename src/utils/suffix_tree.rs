@@ -0,0 +1,369 @@
+use crate::utils::digraph::{Graph, Node as GraphNode};
+use crate::utils::sequence::Sequence;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// A node of the suffix tree, labelled by the edge `text[start..end]` leading into it.
+/// Leaves have no fixed end and always extend to the end of the text.
+#[derive(Debug)]
+struct Node<T> {
+    start: usize,
+    end: Option<usize>,
+    suffix_link: usize,
+    children: BTreeMap<Option<T>, usize>,
+    suffix_index: Option<usize>,
+}
+
+impl<T> Node<T> {
+    fn new(start: usize, end: Option<usize>) -> Self {
+        Self {
+            start,
+            end,
+            suffix_link: 0,
+            children: BTreeMap::new(),
+            suffix_index: None,
+        }
+    }
+}
+
+/// The symbols to the left of every suffix below a node, used to find left-maximal repeats
+#[derive(Clone, Copy, PartialEq)]
+enum LeftContext<T> {
+    Unique(T),
+    Diverse,
+}
+
+/// A suffix tree built with Ukkonen's algorithm. The text is terminated by a
+/// unique end marker, shown as `$` in edge labels.
+#[derive(Debug)]
+pub struct SuffixTree<T> {
+    text: Vec<Option<T>>,
+    nodes: Vec<Node<T>>,
+}
+
+const ROOT: usize = 0;
+
+impl<T: Copy + Ord + Display> SuffixTree<T> {
+    pub fn new<S: Sequence<Symbol = T>>(sequence: &S) -> Self {
+        Self::from_symbols(sequence.symbols())
+    }
+
+    pub fn from_symbols(symbols: &[T]) -> Self {
+        let mut text: Vec<Option<T>> = symbols.iter().map(|&s| Some(s)).collect();
+        text.push(None);
+
+        let mut tree = SuffixTree {
+            text,
+            nodes: vec![Node::new(0, Some(0))],
+        };
+        tree.build();
+        tree.set_suffix_indices();
+        tree
+    }
+
+    fn edge_length(&self, node: usize, current_end: usize) -> usize {
+        let node = &self.nodes[node];
+        node.end.unwrap_or(current_end) - node.start
+    }
+
+    fn add_node(&mut self, start: usize, end: Option<usize>) -> usize {
+        self.nodes.push(Node::new(start, end));
+        self.nodes.len() - 1
+    }
+
+    fn build(&mut self) {
+        let mut active_node = ROOT;
+        let mut active_edge = 0;
+        let mut active_length = 0;
+        let mut remainder = 0;
+
+        for i in 0..self.text.len() {
+            remainder += 1;
+            let mut last_internal: Option<usize> = None;
+
+            while remainder > 0 {
+                if active_length == 0 {
+                    active_edge = i;
+                }
+                let edge_symbol = self.text[active_edge];
+
+                match self.nodes[active_node].children.get(&edge_symbol).copied() {
+                    None => {
+                        let leaf = self.add_node(i, None);
+                        self.nodes[active_node].children.insert(edge_symbol, leaf);
+                        if let Some(internal) = last_internal.take() {
+                            self.nodes[internal].suffix_link = active_node;
+                        }
+                    }
+                    Some(next) => {
+                        let length = self.edge_length(next, i + 1);
+                        if active_length >= length {
+                            active_edge += length;
+                            active_length -= length;
+                            active_node = next;
+                            continue;
+                        }
+
+                        if self.text[self.nodes[next].start + active_length] == self.text[i] {
+                            if let Some(internal) = last_internal.take() {
+                                self.nodes[internal].suffix_link = active_node;
+                            }
+                            active_length += 1;
+                            break;
+                        }
+
+                        let split_start = self.nodes[next].start;
+                        let split = self.add_node(split_start, Some(split_start + active_length));
+                        self.nodes[active_node].children.insert(edge_symbol, split);
+
+                        let leaf = self.add_node(i, None);
+                        self.nodes[split].children.insert(self.text[i], leaf);
+
+                        self.nodes[next].start += active_length;
+                        let next_symbol = self.text[self.nodes[next].start];
+                        self.nodes[split].children.insert(next_symbol, next);
+
+                        if let Some(internal) = last_internal {
+                            self.nodes[internal].suffix_link = split;
+                        }
+                        last_internal = Some(split);
+                    }
+                }
+
+                remainder -= 1;
+                if active_node == ROOT && active_length > 0 {
+                    active_length -= 1;
+                    active_edge = i + 1 - remainder;
+                } else if active_node != ROOT {
+                    active_node = self.nodes[active_node].suffix_link;
+                }
+            }
+        }
+    }
+
+    fn set_suffix_indices(&mut self) {
+        let n = self.text.len();
+        let mut stack = vec![(ROOT, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            let depth = depth + self.edge_length(node, n);
+            if self.nodes[node].children.is_empty() {
+                self.nodes[node].suffix_index = Some(n - depth);
+            }
+            for &child in self.nodes[node].children.values() {
+                stack.push((child, depth));
+            }
+        }
+    }
+
+    /// Nodes in depth-first order paired with their parent
+    fn preorder(&self) -> Vec<(usize, usize)> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack: Vec<(usize, usize)> = self.nodes[ROOT]
+            .children
+            .values()
+            .rev()
+            .map(|&child| (ROOT, child))
+            .collect();
+        while let Some((parent, node)) = stack.pop() {
+            order.push((parent, node));
+            for &child in self.nodes[node].children.values().rev() {
+                stack.push((node, child));
+            }
+        }
+        order
+    }
+
+    fn label(&self, node: usize) -> String {
+        let node = &self.nodes[node];
+        self.text[node.start..node.end.unwrap_or(self.text.len())]
+            .iter()
+            .map(|symbol| match symbol {
+                Some(s) => s.to_string(),
+                None => "$".to_string(),
+            })
+            .collect()
+    }
+
+    /// The labels of every edge in the tree, in depth-first order
+    pub fn edge_labels(&self) -> Vec<String> {
+        self.preorder()
+            .into_iter()
+            .map(|(_, node)| self.label(node))
+            .collect()
+    }
+
+    /// String depth and number of leaves below each node
+    fn depths_and_leaf_counts(&self) -> (Vec<usize>, Vec<usize>) {
+        let order = self.preorder();
+        let mut depths = vec![0; self.nodes.len()];
+        for &(parent, node) in &order {
+            depths[node] = depths[parent] + self.edge_length(node, self.text.len());
+        }
+        let mut leaves = vec![0; self.nodes.len()];
+        for &(parent, node) in order.iter().rev() {
+            if self.nodes[node].children.is_empty() {
+                leaves[node] = 1;
+            }
+            leaves[parent] += leaves[node];
+        }
+        (depths, leaves)
+    }
+
+    /// The symbols spelled from the root down to the end of `node`'s edge
+    fn path_symbols(&self, node: usize, depth: usize) -> Vec<T> {
+        let end = self.nodes[node].end.unwrap_or(self.text.len());
+        self.text[end - depth..end]
+            .iter()
+            .filter_map(|&symbol| symbol)
+            .collect()
+    }
+
+    /// Find the longest substring occurring at least `k` times. Every
+    /// substring occurs at least once, so for `k` of at most 1 this is the
+    /// whole text.
+    pub fn longest_repeat(&self, k: usize) -> Option<Vec<T>> {
+        if k <= 1 {
+            return Some(self.text.iter().filter_map(|&symbol| symbol).collect());
+        }
+        let (depths, leaves) = self.depths_and_leaf_counts();
+        (1..self.nodes.len())
+            .filter(|&node| !self.nodes[node].children.is_empty() && leaves[node] >= k)
+            .max_by_key(|&node| depths[node])
+            .map(|node| self.path_symbols(node, depths[node]))
+    }
+
+    /// Find all repeats of at least `min_length` that cannot be extended in
+    /// either direction without losing an occurrence
+    pub fn maximal_repeats(&self, min_length: usize) -> Vec<Vec<T>> {
+        let order = self.preorder();
+        let (depths, _) = self.depths_and_leaf_counts();
+
+        let mut context: Vec<Option<LeftContext<T>>> = vec![None; self.nodes.len()];
+        for &(parent, node) in order.iter().rev() {
+            if let Some(index) = self.nodes[node].suffix_index {
+                // The suffix starting the text cannot be extended to the left
+                context[node] = match index.checked_sub(1).and_then(|i| self.text[i]) {
+                    Some(left) => Some(LeftContext::Unique(left)),
+                    None => Some(LeftContext::Diverse),
+                };
+            }
+            context[parent] = match (context[parent], context[node]) {
+                (None, child) => child,
+                (Some(current), Some(child)) if current == child => Some(current),
+                _ => Some(LeftContext::Diverse),
+            };
+        }
+
+        let mut repeats: Vec<Vec<T>> = order
+            .iter()
+            .map(|&(_, node)| node)
+            .filter(|&node| {
+                !self.nodes[node].children.is_empty()
+                    && depths[node] >= min_length
+                    && context[node] == Some(LeftContext::Diverse)
+            })
+            .map(|node| self.path_symbols(node, depths[node]))
+            .collect();
+        repeats.sort_by_key(|repeat| std::cmp::Reverse(repeat.len()));
+        repeats
+    }
+
+    /// Number of distinct non-empty substrings of the text
+    pub fn distinct_substrings(&self) -> usize {
+        (1..self.nodes.len())
+            .map(|node| {
+                let length = self.edge_length(node, self.text.len());
+                if self.nodes[node].children.is_empty() {
+                    length - 1
+                } else {
+                    length
+                }
+            })
+            .sum()
+    }
+
+    /// The ratio of distinct substrings to the most possible for a text of
+    /// this length over an alphabet of `alphabet_size` symbols
+    pub fn linguistic_complexity(&self, alphabet_size: usize) -> f64 {
+        let n = self.text.len() - 1;
+        let mut max_substrings = 0;
+        let mut words_of_length = 1usize;
+        for length in 1..=n {
+            words_of_length = words_of_length.saturating_mul(alphabet_size);
+            max_substrings += words_of_length.min(n - length + 1);
+        }
+        self.distinct_substrings() as f64 / max_substrings as f64
+    }
+
+    /// Convert the tree to a graph, with each node labelled by the edge leading into it
+    pub fn to_digraph(&self) -> Graph<GraphNode<String>, ()> {
+        let mut graph = Graph::directed();
+        for node in 0..self.nodes.len() {
+            let label = if node == ROOT {
+                String::new()
+            } else {
+                self.label(node)
+            };
+            graph.add_node(GraphNode::new(format!("node{}", node + 1), label));
+        }
+        for (parent, node) in self.preorder() {
+            graph.add_edge(parent, node, ());
+        }
+        graph
+    }
+
+    /// Render the tree in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        self.to_digraph().to_dot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::Dna;
+    use std::collections::BTreeSet;
+    use std::convert::TryFrom;
+
+    fn naive_distinct_substrings(s: &str) -> usize {
+        let mut substrings = BTreeSet::new();
+        for i in 0..s.len() {
+            for j in i + 1..=s.len() {
+                substrings.insert(&s[i..j]);
+            }
+        }
+        substrings.len()
+    }
+
+    #[test]
+    fn leaves_cover_every_suffix() {
+        let dna = Dna::try_from("ATAAATGACGATTTAAAGGCA").unwrap();
+        let tree = SuffixTree::new(&dna);
+        let mut indices: Vec<usize> = tree.nodes.iter().filter_map(|n| n.suffix_index).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..=dna.len()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn distinct_substrings_match_naive_count() {
+        for s in &["ATTTGGATT", "AAAAAAAA", "ACGTACGTTGCA", "GATATATGCATATACTT"] {
+            let tree = SuffixTree::new(&Dna::try_from(*s).unwrap());
+            assert_eq!(tree.distinct_substrings(), naive_distinct_substrings(s))
+        }
+    }
+
+    #[test]
+    fn longest_repeat_with_overlaps() {
+        let tree = SuffixTree::new(&Dna::try_from("CATACATAC").unwrap());
+        let repeat = tree.longest_repeat(2).unwrap();
+        assert_eq!(
+            repeat.iter().map(|b| b.to_string()).collect::<String>(),
+            "CATAC"
+        );
+        for k in 0..=1 {
+            let whole = tree.longest_repeat(k).unwrap();
+            assert_eq!(Dna::from(whole).to_string(), "CATACATAC");
+        }
+        assert_eq!(tree.longest_repeat(5), None)
+    }
+}
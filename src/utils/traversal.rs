@@ -0,0 +1,344 @@
+use crate::utils::digraph::Graph;
+use std::collections::VecDeque;
+
+/// The number of edges on a shortest path from `source` to each node, or
+/// `None` for nodes that cannot be reached
+pub fn bfs_distances<N, W>(graph: &Graph<N, W>, source: usize) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.node_count()];
+    distances[source] = Some(0);
+    let mut queue = VecDeque::from(vec![source]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node].unwrap();
+        for (other, _) in graph.neighbours(node) {
+            if distances[other].is_none() {
+                distances[other] = Some(distance + 1);
+                queue.push_back(other);
+            }
+        }
+    }
+    distances
+}
+
+/// The visit order of a depth-first search of a whole graph, with the clock
+/// time at which each node was first entered and finally left
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DepthFirstSearch {
+    pre: Vec<usize>,
+    post: Vec<usize>,
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+}
+
+impl DepthFirstSearch {
+    /// Search from each unvisited node in turn, following edges in the order
+    /// they were added
+    pub fn new<N, W>(graph: &Graph<N, W>) -> Self {
+        let nodes = graph.node_count();
+        let mut search = DepthFirstSearch {
+            pre: vec![0; nodes],
+            post: vec![0; nodes],
+            preorder: Vec::with_capacity(nodes),
+            postorder: Vec::with_capacity(nodes),
+        };
+        let mut visited = vec![false; nodes];
+        let mut clock = 0;
+        for start in 0..nodes {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            search.enter(start, &mut clock);
+            let mut stack = vec![(start, graph.neighbours(start))];
+            while let Some((node, neighbours)) = stack.last_mut() {
+                let node = *node;
+                match neighbours.find(|&(other, _)| !visited[other]) {
+                    Some((other, _)) => {
+                        visited[other] = true;
+                        search.enter(other, &mut clock);
+                        stack.push((other, graph.neighbours(other)));
+                    }
+                    None => {
+                        search.post[node] = clock;
+                        search.postorder.push(node);
+                        clock += 1;
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        search
+    }
+
+    fn enter(&mut self, node: usize, clock: &mut usize) {
+        self.pre[node] = *clock;
+        self.preorder.push(node);
+        *clock += 1;
+    }
+
+    /// The clock time at which `node` was first entered
+    pub fn pre(&self, node: usize) -> usize {
+        self.pre[node]
+    }
+
+    /// The clock time at which `node` was left
+    pub fn post(&self, node: usize) -> usize {
+        self.post[node]
+    }
+
+    /// The nodes in the order they were entered
+    pub fn preorder(&self) -> &[usize] {
+        &self.preorder
+    }
+
+    /// The nodes in the order they were left
+    pub fn postorder(&self) -> &[usize] {
+        &self.postorder
+    }
+}
+
+/// The connected components of an undirected graph, each sorted, ordered by
+/// their smallest node
+pub fn connected_components<N, W>(graph: &Graph<N, W>) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; graph.node_count()];
+    let mut components = Vec::new();
+    for start in 0..graph.node_count() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for (other, _) in graph.neighbours(node) {
+                if !seen[other] {
+                    seen[other] = true;
+                    component.push(other);
+                    stack.push(other);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    components
+}
+
+/// Order the nodes of a directed graph so that every edge points forwards.
+/// If the graph has a cycle, the nodes of one cycle are returned instead, in
+/// the order they are joined.
+pub fn topological_sort<N, W>(graph: &Graph<N, W>) -> Result<Vec<usize>, Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        New,
+        Active,
+        Done,
+    }
+
+    let mut state = vec![State::New; graph.node_count()];
+    let mut order = Vec::with_capacity(graph.node_count());
+    for start in 0..graph.node_count() {
+        if state[start] != State::New {
+            continue;
+        }
+        state[start] = State::Active;
+        let mut stack = vec![(start, graph.neighbours(start))];
+        while let Some((node, neighbours)) = stack.last_mut() {
+            let node = *node;
+            match neighbours.find(|&(other, _)| state[other] != State::Done) {
+                // The active nodes are the path on the stack, so meeting one
+                // again closes a cycle
+                Some((other, _)) if state[other] == State::Active => {
+                    let position = stack.iter().position(|&(n, _)| n == other).unwrap();
+                    return Err(stack[position..].iter().map(|&(n, _)| n).collect());
+                }
+                Some((other, _)) => {
+                    state[other] = State::Active;
+                    stack.push((other, graph.neighbours(other)));
+                }
+                None => {
+                    state[node] = State::Done;
+                    order.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    order.reverse();
+    Ok(order)
+}
+
+/// Whether a directed graph has no cycles
+pub fn is_acyclic<N, W>(graph: &Graph<N, W>) -> bool {
+    topological_sort(graph).is_ok()
+}
+
+/// A colouring of an undirected graph's nodes with two colours so that no
+/// edge joins nodes of the same colour, if there is one. The smallest node
+/// of each component is coloured `false`.
+pub fn two_colouring<N, W>(graph: &Graph<N, W>) -> Option<Vec<bool>> {
+    let mut colours: Vec<Option<bool>> = vec![None; graph.node_count()];
+    for start in 0..graph.node_count() {
+        if colours[start].is_some() {
+            continue;
+        }
+        colours[start] = Some(false);
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(node) = queue.pop_front() {
+            let colour = colours[node].unwrap();
+            for (other, _) in graph.neighbours(node) {
+                match colours[other] {
+                    None => {
+                        colours[other] = Some(!colour);
+                        queue.push_back(other);
+                    }
+                    Some(other_colour) if other_colour == colour => return None,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    colours.into_iter().collect()
+}
+
+/// Whether an undirected graph's nodes can be split into two sets with no
+/// edges inside either set
+pub fn is_bipartite<N, W>(graph: &Graph<N, W>) -> bool {
+    two_colouring(graph).is_some()
+}
+
+/// The strongly connected components of a directed graph by Kosaraju's
+/// algorithm, each sorted. The components are in topological order, so no
+/// edge leads from a component to an earlier one.
+pub fn strongly_connected_components<N, W>(graph: &Graph<N, W>) -> Vec<Vec<usize>>
+where
+    N: Clone,
+    W: Clone,
+{
+    // The last node left in a search of the reversed graph lies in a
+    // component with no edges out of it in the original graph
+    let reversed_search = DepthFirstSearch::new(&graph.reversed());
+    let mut assigned = vec![false; graph.node_count()];
+    let mut components = Vec::new();
+    for &start in reversed_search.postorder().iter().rev() {
+        if assigned[start] {
+            continue;
+        }
+        assigned[start] = true;
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for (other, _) in graph.neighbours(node) {
+                if !assigned[other] {
+                    assigned[other] = true;
+                    component.push(other);
+                    stack.push(other);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    // Components were found sinks first
+    components.reverse();
+    components
+}
+
+/// The graph with each strongly connected component merged into one node,
+/// whose payload is its members. Nodes are in topological order, and there is
+/// at most one edge between any two of them.
+pub fn condensation<N, W>(graph: &Graph<N, W>) -> Graph<Vec<usize>, ()>
+where
+    N: Clone,
+    W: Clone,
+{
+    let components = strongly_connected_components(graph);
+    let mut component_of = vec![0; graph.node_count()];
+    for (i, component) in components.iter().enumerate() {
+        for &node in component {
+            component_of[node] = i;
+        }
+    }
+    let mut links: Vec<(usize, usize)> = graph
+        .edges()
+        .iter()
+        .map(|edge| (component_of[edge.from], component_of[edge.to]))
+        .filter(|(from, to)| from != to)
+        .collect();
+    links.sort_unstable();
+    links.dedup();
+
+    let mut condensed = Graph::directed();
+    for component in components {
+        condensed.add_node(component);
+    }
+    for (from, to) in links {
+        condensed.add_edge(from, to, ());
+    }
+    condensed
+}
+
+/// Whether for every pair of nodes in a directed graph, there is a path from
+/// one to the other in at least one direction. This holds exactly when the
+/// topologically ordered components are joined in a chain.
+pub fn is_semi_connected<N, W>(graph: &Graph<N, W>) -> bool
+where
+    N: Clone,
+    W: Clone,
+{
+    let condensed = condensation(graph);
+    (1..condensed.node_count()).all(|i| condensed.neighbours(i - 1).any(|(other, _)| other == i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_first_clocks() {
+        let graph = Graph::from_edge_list("5 4\n1 2\n2 3\n1 4\n5 4", true).unwrap();
+        let search = DepthFirstSearch::new(&graph);
+        assert_eq!(search.preorder(), &[0, 1, 2, 3, 4]);
+        assert_eq!(search.postorder(), &[2, 1, 3, 0, 4]);
+        assert_eq!((search.pre(0), search.post(0)), (0, 7));
+        assert_eq!((search.pre(3), search.post(3)), (5, 6));
+        assert_eq!((search.pre(4), search.post(4)), (8, 9));
+        assert_eq!(
+            bfs_distances(&graph, 1),
+            vec![None, Some(0), Some(1), None, None]
+        )
+    }
+
+    #[test]
+    fn cycles_and_colourings() {
+        let cyclic = Graph::from_edge_list("4 4\n1 2\n2 3\n3 4\n4 2", true).unwrap();
+        assert_eq!(topological_sort(&cyclic), Err(vec![1, 2, 3]));
+        assert!(!is_acyclic(&cyclic));
+
+        let square = Graph::from_edge_list("5 4\n1 2\n2 3\n3 4\n4 1", false).unwrap();
+        assert_eq!(
+            two_colouring(&square),
+            Some(vec![false, true, false, true, false])
+        );
+        assert_eq!(
+            connected_components(&square),
+            vec![vec![0, 1, 2, 3], vec![4]]
+        );
+        let triangle = Graph::from_edge_list("3 3\n1 2\n2 3\n3 1", false).unwrap();
+        assert!(!is_bipartite(&triangle))
+    }
+
+    #[test]
+    fn strong_components_and_semi_connectivity() {
+        let graph = Graph::from_edge_list("5 6\n1 2\n2 1\n2 3\n4 3\n3 5\n5 4", true).unwrap();
+        assert_eq!(
+            strongly_connected_components(&graph),
+            vec![vec![0, 1], vec![2, 3, 4]]
+        );
+        let condensed = condensation(&graph);
+        assert_eq!(condensed.edge_count(), 1);
+        assert!(is_semi_connected(&graph));
+        let forked = Graph::from_edge_list("3 2\n1 2\n1 3", true).unwrap();
+        assert_eq!(strongly_connected_components(&forked).len(), 3);
+        assert!(!is_semi_connected(&forked))
+    }
+}
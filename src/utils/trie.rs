@@ -0,0 +1,161 @@
+use crate::utils::digraph::{Graph, Node as GraphNode};
+use crate::utils::fasta::Fasta;
+use crate::utils::sequence::Sequence;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+#[derive(Debug)]
+struct Node<T> {
+    children: BTreeMap<T, usize>,
+    parent: Option<(usize, T)>,
+    pattern_end: bool,
+}
+
+impl<T> Node<T> {
+    fn new(parent: Option<(usize, T)>) -> Self {
+        Self {
+            children: BTreeMap::new(),
+            parent,
+            pattern_end: false,
+        }
+    }
+}
+
+/// A prefix trie over a collection of patterns. Nodes are numbered in the
+/// order they are created, with the root as node 0.
+#[derive(Debug)]
+pub struct Trie<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T: Copy + Ord + Display> Trie<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(None)],
+        }
+    }
+
+    pub fn from_patterns<S: Sequence<Symbol = T>>(patterns: &[S]) -> Self {
+        let mut trie = Self::new();
+        for pattern in patterns {
+            trie.insert(pattern.symbols());
+        }
+        trie
+    }
+
+    pub fn from_fasta<S: Sequence<Symbol = T>>(fasta: &Fasta<S>) -> Self {
+        let mut trie = Self::new();
+        for (_, pattern) in fasta.iter() {
+            trie.insert(pattern.symbols());
+        }
+        trie
+    }
+
+    pub fn insert(&mut self, pattern: &[T]) {
+        let mut node = 0;
+        for &symbol in pattern {
+            node = match self.nodes[node].children.get(&symbol) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(Node::new(Some((node, symbol))));
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(symbol, child);
+                    child
+                }
+            }
+        }
+        self.nodes[node].pattern_end = true;
+    }
+
+    /// Number of nodes in the trie, including the root
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 1
+    }
+
+    /// Edges as `(parent, child, symbol)` in order of creation
+    pub fn adjacency_list(&self) -> Vec<(usize, usize, T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(child, node)| node.parent.map(|(parent, symbol)| (parent, child, symbol)))
+            .collect()
+    }
+
+    /// The length of the shortest pattern that is a prefix of `text`
+    pub fn prefix_match(&self, text: &[T]) -> Option<usize> {
+        let mut node = 0;
+        for (i, symbol) in text.iter().enumerate() {
+            node = *self.nodes[node].children.get(symbol)?;
+            if self.nodes[node].pattern_end {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+
+    /// Find the 0-based positions in `text` at which some pattern starts
+    pub fn matches(&self, text: &[T]) -> Vec<usize> {
+        (0..text.len())
+            .filter(|&i| self.prefix_match(&text[i..]).is_some())
+            .collect()
+    }
+
+    /// Convert the trie to a graph, with nodes numbered from 1 and labelled
+    /// by the symbol on the edge leading into them
+    pub fn to_digraph(&self) -> Graph<GraphNode<String>, ()> {
+        let mut graph = Graph::directed();
+        for (node, trie_node) in self.nodes.iter().enumerate() {
+            let label = trie_node
+                .parent
+                .map(|(_, symbol)| symbol.to_string())
+                .unwrap_or_default();
+            graph.add_node(GraphNode::new((node + 1).to_string(), label));
+        }
+        for (parent, child, _) in self.adjacency_list() {
+            graph.add_edge(parent, child, ());
+        }
+        graph
+    }
+}
+
+impl<T: Copy + Ord + Display> Default for Trie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dna::Dna;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn matches_finds_every_pattern_start() {
+        let patterns = vec![
+            Dna::try_from("ATCG").unwrap(),
+            Dna::try_from("GGGT").unwrap(),
+        ];
+        let trie = Trie::from_patterns(&patterns);
+        let text = Dna::try_from("AATCGGGTTCAATCGGGGT").unwrap();
+        assert_eq!(trie.matches(text.sequence()), vec![1, 4, 11, 15])
+    }
+
+    #[test]
+    fn shortest_pattern_is_matched_first() {
+        let patterns = vec![Dna::try_from("AC").unwrap(), Dna::try_from("ACGT").unwrap()];
+        let trie = Trie::from_patterns(&patterns);
+        assert_eq!(
+            trie.prefix_match(Dna::try_from("ACGTA").unwrap().sequence()),
+            Some(2)
+        );
+        assert_eq!(
+            trie.prefix_match(Dna::try_from("AGT").unwrap().sequence()),
+            None
+        )
+    }
+}